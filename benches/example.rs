@@ -13,6 +13,7 @@ fn multi_threaded(c: &mut Criterion) {
     c.bench_function("multi threaded", |b| b.iter(|| get_best_moves_multi(black_box(game), 0, true, 0)));
 }
 
+#[allow(dead_code, unused_variables)]
 fn multi_threaded_no_workers(c: &mut Criterion) {
     
 }
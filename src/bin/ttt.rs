@@ -0,0 +1,170 @@
+//! Play tic tac toe against the engine in the terminal.
+//!
+//! ```text
+//! cargo run --release --bin ttt -- --game 4 --side o --depth 6 --analysis
+//! ```
+
+use multithread_minimax::example::{four_by_four, three_by_three};
+use multithread_minimax::{
+    try_get_best_moves_multi, Board, Result as _, SearchObserver, SearchOptions,
+};
+use std::fmt::Debug;
+use std::io::{self, BufRead, Write};
+use std::process;
+use std::sync::Arc;
+
+const USAGE: &str = "\
+usage: ttt [options]
+
+options:
+    --game 3|4       play on a 3x3 or 4x4 board (default 3)
+    --side x|o       play as x, who moves first, or as o (default x)
+    --depth N        plies the engine searches, 0 for no limit
+                     (default 0 on 3x3 and 5 on 4x4)
+    --threads N      threads the engine searches with, 0 for one per cpu
+                     (default 0)
+    --analysis       show the engine's score for every move it considers
+    --help           show this message
+
+Enter the number of a square to move there, or quit to stop.";
+
+struct Config {
+    game: u8,
+    human_is_max: bool,
+    depth: Option<u16>,
+    threads: usize,
+    analysis: bool,
+}
+
+/// The example games both number their squares the same way
+trait Squares: Board + 'static {
+    fn square(game_move: &Self::Move) -> usize;
+}
+
+impl Squares for three_by_three::TTT {
+    fn square(game_move: &three_by_three::Move) -> usize {
+        game_move.to_position
+    }
+}
+
+impl Squares for four_by_four::TTT {
+    fn square(game_move: &four_by_four::Move) -> usize {
+        game_move.to_position
+    }
+}
+
+/// Prints the score of each move the engine searches
+struct Analysis;
+
+impl SearchObserver for Analysis {
+    fn root_move_searched(&self, game_move: &dyn Debug, score: &dyn Debug) {
+        println!("  {:?}: {:?}", game_move, score);
+    }
+}
+
+fn main() {
+    let config = parse_args(std::env::args().skip(1)).unwrap_or_else(|message| {
+        eprintln!("{}\n\n{}", message, USAGE);
+        process::exit(2);
+    });
+    let played = match config.game {
+        3 => play(three_by_three::TTT::new('x', 'o'), &config, 0),
+        _ => play(four_by_four::TTT::new('x', 'o'), &config, 5),
+    };
+    if let Err(e) = played {
+        eprintln!("{}", e);
+        process::exit(1);
+    }
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Config, String> {
+    let mut config = Config {
+        game: 3,
+        human_is_max: true,
+        depth: None,
+        threads: 0,
+        analysis: false,
+    };
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("{} needs a value", arg));
+        match arg.as_str() {
+            "--game" => {
+                config.game = match value()?.as_str() {
+                    "3" | "3x3" => 3,
+                    "4" | "4x4" => 4,
+                    other => return Err(format!("unknown game {}", other)),
+                }
+            }
+            "--side" => {
+                config.human_is_max = match value()?.as_str() {
+                    "x" => true,
+                    "o" => false,
+                    other => return Err(format!("unknown side {}", other)),
+                }
+            }
+            "--depth" => {
+                config.depth = Some(value()?.parse().map_err(|_| "--depth needs a number")?);
+            }
+            "--threads" => {
+                config.threads = value()?.parse().map_err(|_| "--threads needs a number")?;
+            }
+            "--analysis" => config.analysis = true,
+            "--help" => {
+                println!("{}", USAGE);
+                process::exit(0);
+            }
+            other => return Err(format!("unknown option {}", other)),
+        }
+    }
+    Ok(config)
+}
+
+fn play<T: Squares>(mut board: T, config: &Config, default_depth: u16) -> io::Result<()> {
+    let depth = config.depth.unwrap_or(default_depth);
+    let mut options = SearchOptions::default();
+    if config.analysis {
+        options.observer = Some(Arc::new(Analysis));
+    }
+    let mut lines = io::stdin().lock().lines();
+    let mut is_max = true;
+    while !board.evaluate().is_over() {
+        println!("{}\n", board);
+        let game_move = if is_max == config.human_is_max {
+            let valid = board.get_valid_moves(is_max);
+            loop {
+                print!("your move: ");
+                io::stdout().flush()?;
+                let Some(line) = lines.next().transpose()? else { return Ok(()) };
+                let line = line.trim();
+                if line == "quit" {
+                    return Ok(());
+                }
+                let square = line.parse().ok();
+                match valid.iter().find(|m| Some(T::square(m)) == square) {
+                    Some(m) => break *m,
+                    None => println!("{} is not a free square", line),
+                }
+            }
+        } else {
+            let searched = try_get_best_moves_multi(board.clone(), depth, is_max, config.threads, &options);
+            let (moves, metadata) = searched.map_err(|e| io::Error::other(e.to_string()))?;
+            let best = &moves[0];
+            println!("engine plays {} ({:?})", T::square(&best.game_move), best.score);
+            if config.analysis {
+                println!("  {}", metadata.stats());
+            }
+            best.game_move
+        };
+        board.make_move(&game_move);
+        is_max = !is_max;
+    }
+    println!("{}\n", board);
+    let result = board.evaluate();
+    let outcome = match result.winner() {
+        None => "draw",
+        Some(winner) if winner == config.human_is_max => "you win",
+        Some(_) => "engine wins",
+    };
+    println!("{}", outcome);
+    Ok(())
+}
@@ -0,0 +1,294 @@
+use crate::{
+    moves_or_pass, try_get_best_moves, try_get_best_moves_scoped, Board, Metadata, MoveScore,
+    Result, SearchError, SearchOptions,
+};
+use std::collections::HashMap;
+use std::fs::File;
+use std::hash::{Hash, Hasher};
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+const MAGIC: &[u8; 4] = b"MMBK";
+const VERSION: u8 = 1;
+
+/// A move recommended by an [OpeningBook]. Moves with a higher weight
+/// should be played more often.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BookMove<M> {
+    pub game_move: M,
+    pub weight: u32,
+}
+
+/// Recommends moves for known positions so they do not need to be
+/// searched, see [try_get_best_moves_with_book]
+pub trait OpeningBook<T: Board> {
+    /// The recommended moves for the player to move, or an empty vector
+    /// if the position is not in the book
+    fn lookup(&self, board: &T, is_maximizers_turn: bool) -> Vec<BookMove<T::Move>>;
+}
+
+/// An [OpeningBook] that can be saved to and loaded from a file.
+/// Positions are stored as a hash of the board and the player to move,
+/// and moves by their index in [Board::get_valid_moves] (a forced pass
+/// is index 0), so any board that implements [Hash] can be stored
+/// without a way to write out its positions or moves.
+///
+/// Hashes depend on the [Hash] implementation, so a book only works for
+/// the board type it was built for. Derived implementations write
+/// lengths and enum discriminants in the native width and byte order,
+/// and std does not promise their output stays the same between
+/// releases, so a saved book is only portable to the same platform and
+/// toolchain it was built with. Rebuild the book anywhere else.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Book {
+    entries: HashMap<u64, Vec<(u16, u32)>>,
+}
+
+/// Where the moves returned by [try_get_best_moves_with_book] came from
+#[derive(Debug)]
+pub enum Recommendation<T: Board> {
+    Book(Vec<BookMove<T::Move>>),
+    Searched(Vec<MoveScore<T>>, Metadata),
+}
+
+impl<T: Board> Recommendation<T> {
+    pub fn moves(&self) -> Vec<T::Move> {
+        match self {
+            Recommendation::Book(moves) => moves.iter().map(|m| m.game_move).collect(),
+            Recommendation::Searched(moves, _) => moves.iter().map(|m| m.game_move).collect(),
+        }
+    }
+}
+
+/// Returns the moves `book` recommends for `board` if it has any,
+/// otherwise searches it with [try_get_best_moves]
+pub fn try_get_best_moves_with_book<T: Board>(
+    board: T,
+    max_depth: u16,
+    is_maximizers_turn: bool,
+    book: &dyn OpeningBook<T>,
+    options: &SearchOptions
+) -> std::result::Result<Recommendation<T>, SearchError> {
+    let moves = book.lookup(&board, is_maximizers_turn);
+    if !moves.is_empty() {
+        return Ok(Recommendation::Book(moves));
+    }
+    let (moves, metadata) = try_get_best_moves(board, max_depth, is_maximizers_turn, options)?;
+    Ok(Recommendation::Searched(moves, metadata))
+}
+
+/// FNV-1a, which unlike the std hasher is not seeded randomly, so the
+/// same bytes always give the same key
+struct Fnv(u64);
+
+impl Hasher for Fnv {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= *byte as u64;
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+}
+
+fn key<T: Hash>(board: &T, is_maximizers_turn: bool) -> u64 {
+    let mut hasher = Fnv(0xcbf2_9ce4_8422_2325);
+    board.hash(&mut hasher);
+    is_maximizers_turn.hash(&mut hasher);
+    hasher.finish()
+}
+
+impl Book {
+    pub fn new() -> Book {
+        Book::default()
+    }
+
+    /// The number of positions in the book
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Recommends `moves` for `board`, replacing any moves already in the
+    /// book for it. Moves that are not valid in the position are left out.
+    pub fn insert<T>(&mut self, board: &T, is_maximizers_turn: bool, moves: &[BookMove<T::Move>])
+    where
+        T: Board + Hash,
+        T::Move: PartialEq,
+    {
+        let valid = moves_or_pass(board, is_maximizers_turn);
+        let indices = moves
+            .iter()
+            .filter_map(|m| {
+                let index = valid.iter().position(|valid| *valid == m.game_move)?;
+                Some((index as u16, m.weight))
+            })
+            .collect();
+        self.entries.insert(key(board, is_maximizers_turn), indices);
+    }
+
+    /// Writes the book in its file format: the bytes `MMBK`, a version
+    /// byte and the number of positions, then for each position its
+    /// hash, the number of moves and the index and weight of each move.
+    /// Numbers are little endian, with 32 bits for counts and weights,
+    /// 64 for hashes, 16 for indices and 8 for the number of moves.
+    pub fn write_to(&self, mut writer: impl Write) -> io::Result<()> {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&[VERSION])?;
+        writer.write_all(&(self.entries.len() as u32).to_le_bytes())?;
+        // sorted so the same book always gives the same file
+        let mut keys: Vec<&u64> = self.entries.keys().collect();
+        keys.sort();
+        for key in keys {
+            let moves = &self.entries[key];
+            let count = u8::try_from(moves.len()).map_err(|_| {
+                io::Error::new(io::ErrorKind::InvalidInput, "more than 255 moves in a position")
+            })?;
+            writer.write_all(&key.to_le_bytes())?;
+            writer.write_all(&[count])?;
+            for (index, weight) in moves {
+                writer.write_all(&index.to_le_bytes())?;
+                writer.write_all(&weight.to_le_bytes())?;
+            }
+        }
+        writer.flush()
+    }
+
+    /// Reads a book written by [Book::write_to]
+    pub fn read_from(mut reader: impl Read) -> io::Result<Book> {
+        let mut magic = [0; 5];
+        reader.read_exact(&mut magic)?;
+        if magic[..4] != MAGIC[..] || magic[4] != VERSION {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "not an opening book"));
+        }
+        let mut entries = HashMap::new();
+        for _ in 0..u32::from_le_bytes(read(&mut reader)?) {
+            let key = u64::from_le_bytes(read(&mut reader)?);
+            let [count] = read(&mut reader)?;
+            let moves = (0..count)
+                .map(|_| {
+                    let index = u16::from_le_bytes(read(&mut reader)?);
+                    let weight = u32::from_le_bytes(read(&mut reader)?);
+                    Ok((index, weight))
+                })
+                .collect::<io::Result<Vec<_>>>()?;
+            entries.insert(key, moves);
+        }
+        Ok(Book { entries })
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        self.write_to(BufWriter::new(File::create(path)?))
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Book> {
+        Book::read_from(BufReader::new(File::open(path)?))
+    }
+}
+
+fn read<const N: usize>(reader: &mut impl Read) -> io::Result<[u8; N]> {
+    let mut bytes = [0; N];
+    reader.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
+
+impl<T: Board + Hash> OpeningBook<T> for Book {
+    fn lookup(&self, board: &T, is_maximizers_turn: bool) -> Vec<BookMove<T::Move>> {
+        let Some(moves) = self.entries.get(&key(board, is_maximizers_turn)) else {
+            return vec![];
+        };
+        let valid = moves_or_pass(board, is_maximizers_turn);
+        moves
+            .iter()
+            .filter_map(|(index, weight)| {
+                let game_move = *valid.get(*index as usize)?;
+                Some(BookMove { game_move, weight: *weight })
+            })
+            .collect()
+    }
+}
+
+/// Builds a [Book] by searching every position up to `plies` moves from
+/// a starting position. Each position gets all of its best moves with
+/// the same weight. This runs a deep search for every position, so it
+/// is meant to be done once ahead of time and the book saved.
+#[derive(Clone, Debug)]
+pub struct BookBuilder {
+    /// How many moves from the starting position to cover
+    pub plies: u16,
+    /// How deep to search each position, 0 for no limit
+    pub search_depth: u16,
+    /// Threads for each search, 0 for one per cpu
+    pub threads: usize,
+    pub options: SearchOptions,
+}
+
+impl Default for BookBuilder {
+    fn default() -> Self {
+        BookBuilder { plies: 2, search_depth: 0, threads: 0, options: SearchOptions::default() }
+    }
+}
+
+impl BookBuilder {
+    pub fn build<T>(&self, board: &T, is_maximizers_turn: bool) -> std::result::Result<Book, SearchError>
+    where
+        T: Board + Hash,
+        T::Move: PartialEq,
+    {
+        let mut book = Book::new();
+        let mut expanded = HashMap::new();
+        self.add(&mut book, &mut expanded, &mut board.clone(), is_maximizers_turn, 0)?;
+        Ok(book)
+    }
+
+    /// Adds `board` and the positions after it to `book`. `expanded`
+    /// holds the fewest plies each position was reached in, since a
+    /// position reached again in fewer plies, through a pass or a move
+    /// that is taken back, has room for more positions after it.
+    fn add<T>(
+        &self,
+        book: &mut Book,
+        expanded: &mut HashMap<u64, u16>,
+        board: &mut T,
+        is_maximizers_turn: bool,
+        ply: u16
+    ) -> std::result::Result<(), SearchError>
+    where
+        T: Board + Hash,
+        T::Move: PartialEq,
+    {
+        let key = key(board, is_maximizers_turn);
+        if ply == self.plies
+            || board.evaluate().is_over()
+            || expanded.get(&key).is_some_and(|reached| *reached <= ply)
+        {
+            return Ok(());
+        }
+        expanded.insert(key, ply);
+        // transpositions are only searched once
+        if !book.entries.contains_key(&key) {
+            let (best, _) = try_get_best_moves_scoped(
+                board.clone(),
+                self.search_depth,
+                is_maximizers_turn,
+                self.threads,
+                &self.options
+            )?;
+            let moves: Vec<_> = best.iter().map(|m| BookMove { game_move: m.game_move, weight: 1 }).collect();
+            book.insert(board, is_maximizers_turn, &moves);
+        }
+        for m in moves_or_pass(board, is_maximizers_turn) {
+            let undo = board.make_move(&m);
+            let added = self.add(book, expanded, board, !is_maximizers_turn, ply + 1);
+            board.unmake_move(&m, undo);
+            added?;
+        }
+        Ok(())
+    }
+}
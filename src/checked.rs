@@ -0,0 +1,174 @@
+use crate::{Board, SearchError};
+use std::fmt::{self, Debug, Display};
+use std::sync::{Arc, Mutex};
+
+/// The kinds of mistakes in a [Board] implementation that [Checked]
+/// can find
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ViolationKind {
+    /// [Board::unmake_move] did not restore the board to exactly what
+    /// it was before [Board::make_move]
+    NotRestored,
+    /// [Board::unmake_move] was called with a different move than the
+    /// one that was made
+    WrongMoveUnmade,
+    /// [Board::get_valid_moves] returned the same move more than once
+    DuplicateMove,
+    /// [Board::fill_valid_moves] and [Board::get_valid_moves] disagree
+    FillMismatch,
+}
+
+/// The first mistake found by [Checked], with the move and position
+/// it was found at
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    pub kind: ViolationKind,
+    /// The offending move, formatted with [Debug]
+    pub game_move: String,
+    /// The position the move was made or generated in, formatted with
+    /// [Display]
+    pub position: String,
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let description = match self.kind {
+            ViolationKind::NotRestored => "unmake_move did not restore the board after",
+            ViolationKind::WrongMoveUnmade => "unmake_move was called with a different move than",
+            ViolationKind::DuplicateMove => "get_valid_moves returned more than once",
+            ViolationKind::FillMismatch => "fill_valid_moves and get_valid_moves disagree on",
+        };
+        write!(f, "{} {} in position {}", description, self.game_move, self.position)
+    }
+}
+
+/// Wraps a board to check the [Board] implementation while it is being
+/// searched: every [Board::unmake_move] must restore the board exactly,
+/// and generated moves must be unique. These checks clone the board at
+/// every move, so `Checked` is meant for tests and debugging. Clones
+/// share the first [Violation] found, which can be read with
+/// [Checked::violation] after a search, including a multi threaded one.
+#[derive(Clone, Debug)]
+pub struct Checked<T: Board> {
+    board: T,
+    violation: Arc<Mutex<Option<Violation>>>,
+}
+
+/// The [Board::Undo] of [Checked], which keeps a copy of the board from
+/// before the move to compare against
+pub struct CheckedUndo<T: Board> {
+    undo: T::Undo,
+    before: T,
+    made: T::Move,
+}
+
+impl<T: Board> Checked<T> {
+    pub fn new(board: T) -> Checked<T> {
+        Checked { board, violation: Arc::new(Mutex::new(None)) }
+    }
+
+    pub fn board(&self) -> &T {
+        &self.board
+    }
+
+    pub fn into_inner(self) -> T {
+        self.board
+    }
+
+    /// Returns the first violation found by this board or its clones
+    pub fn violation(&self) -> Option<Violation> {
+        self.violation.lock().unwrap().clone()
+    }
+
+    fn report(&self, kind: ViolationKind, game_move: &T::Move, position: &T) {
+        let mut violation = self.violation.lock().unwrap();
+        if violation.is_none() {
+            *violation = Some(Violation {
+                kind,
+                game_move: format!("{:?}", game_move),
+                position: position.to_string(),
+            });
+        }
+    }
+}
+
+impl<T> Checked<T>
+where
+    T: Board + PartialEq,
+    T::Move: PartialEq,
+{
+    fn check_unique(&self, moves: &[T::Move]) {
+        for (i, m) in moves.iter().enumerate() {
+            if moves[..i].contains(m) {
+                self.report(ViolationKind::DuplicateMove, m, &self.board);
+            }
+        }
+    }
+}
+
+impl<T: Board> Display for Checked<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.board, f)
+    }
+}
+
+impl<T> Board for Checked<T>
+where
+    T: Board + PartialEq,
+    T::Move: PartialEq,
+{
+    type Move = T::Move;
+    type Result = T::Result;
+    type Undo = CheckedUndo<T>;
+
+    fn make_move(&mut self, valid_move: &Self::Move) -> Self::Undo {
+        let before = self.board.clone();
+        let undo = self.board.make_move(valid_move);
+        CheckedUndo { undo, before, made: *valid_move }
+    }
+
+    fn unmake_move(&mut self, made_move: &Self::Move, undo: Self::Undo) {
+        if *made_move != undo.made {
+            self.report(ViolationKind::WrongMoveUnmade, made_move, &undo.before);
+        }
+        self.board.unmake_move(made_move, undo.undo);
+        if self.board != undo.before {
+            self.report(ViolationKind::NotRestored, made_move, &undo.before);
+            // keep searching from the right position
+            self.board = undo.before;
+        }
+    }
+
+    fn get_valid_moves(&self, is_maximizer: bool) -> Vec<Self::Move> {
+        let moves = self.board.get_valid_moves(is_maximizer);
+        self.check_unique(&moves);
+        moves
+    }
+
+    fn fill_valid_moves(&self, is_maximizer: bool, moves: &mut Vec<Self::Move>) {
+        self.board.fill_valid_moves(is_maximizer, moves);
+        self.check_unique(moves);
+        let expected = self.board.get_valid_moves(is_maximizer);
+        let mismatch = moves
+            .iter()
+            .find(|m| !expected.contains(m))
+            .or_else(|| expected.iter().find(|m| !moves.contains(m)));
+        if let Some(m) = mismatch {
+            self.report(ViolationKind::FillMismatch, m, &self.board);
+        }
+    }
+
+    fn pass_move(&self, is_maximizer: bool) -> Option<Self::Move> {
+        self.board.pass_move(is_maximizer)
+    }
+
+    fn evaluate(&self) -> Self::Result {
+        self.board.evaluate()
+    }
+}
+
+impl From<Violation> for SearchError {
+    fn from(violation: Violation) -> SearchError {
+        SearchError::InvalidBoard(violation)
+    }
+}
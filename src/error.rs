@@ -0,0 +1,49 @@
+use crate::Violation;
+use std::any::Any;
+use std::error::Error;
+use std::fmt::{self, Display};
+
+/// Returned by the `try_` search functions instead of panicking
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SearchError {
+    /// The game is not over but the player to move has no valid moves
+    NoLegalMoves,
+    /// A thread panicked while searching. Holds the panic message.
+    WorkerPanicked(String),
+    /// The thread pool stopped before every job was run
+    PoolShutdown,
+    /// The search was stopped through [crate::SearchOptions::stop]
+    Cancelled,
+    /// The search visited [crate::SearchOptions::max_nodes] positions
+    /// or ran past [crate::SearchOptions::deadline]
+    BudgetExhausted,
+    /// A [crate::Checked] board found a mistake in the [crate::Board]
+    /// implementation
+    InvalidBoard(Violation),
+}
+
+impl Display for SearchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SearchError::NoLegalMoves => f.write_str("no legal moves in a position that is not over"),
+            SearchError::WorkerPanicked(message) => write!(f, "search thread panicked: {}", message),
+            SearchError::PoolShutdown => f.write_str("thread pool shut down"),
+            SearchError::Cancelled => f.write_str("search cancelled"),
+            SearchError::BudgetExhausted => f.write_str("search budget exhausted"),
+            SearchError::InvalidBoard(violation) => write!(f, "invalid board: {}", violation),
+        }
+    }
+}
+
+impl Error for SearchError {}
+
+/// Gets the message out of the payload of a caught panic
+pub(crate) fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        String::from("unknown panic")
+    }
+}
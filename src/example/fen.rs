@@ -0,0 +1,140 @@
+//! The notation shared by both tic tac toe boards. Positions are
+//! written like chess FEN: rows from the top separated by `/`, with
+//! runs of empty squares as their length, then the player to move and
+//! the maximizer and minimizer. `x2/1o1/3 x xo` is a 3x3 board with x
+//! in the top left corner and o in the center, with x to move. Moves
+//! are the player followed by the square, like `x4`.
+
+use crate::NotationError;
+
+/// A position read by [parse]
+pub(super) struct Position {
+    pub cells: Vec<Option<char>>,
+    pub maximizer: char,
+    pub minimizer: char,
+    pub is_maximizers_turn: bool,
+}
+
+fn error(message: String) -> NotationError {
+    NotationError(message)
+}
+
+fn valid_symbol(c: char) -> bool {
+    !c.is_ascii_digit() && !c.is_whitespace() && c != '/'
+}
+
+pub(super) fn format(
+    cells: &[Option<char>],
+    width: usize,
+    maximizer: char,
+    minimizer: char,
+    is_maximizers_turn: bool
+) -> String {
+    let rows: Vec<String> = cells
+        .chunks(width)
+        .map(|row| {
+            let mut text = String::new();
+            let mut empty = 0;
+            for cell in row {
+                match cell {
+                    Some(player) => {
+                        if empty > 0 {
+                            text.push_str(&empty.to_string());
+                            empty = 0;
+                        }
+                        text.push(*player);
+                    }
+                    None => empty += 1,
+                }
+            }
+            if empty > 0 {
+                text.push_str(&empty.to_string());
+            }
+            text
+        })
+        .collect();
+    let to_move = if is_maximizers_turn { maximizer } else { minimizer };
+    format!("{} {} {}{}", rows.join("/"), to_move, maximizer, minimizer)
+}
+
+pub(super) fn parse(text: &str, width: usize) -> std::result::Result<Position, NotationError> {
+    let fields: Vec<&str> = text.split_whitespace().collect();
+    let [rows, to_move, players] = fields[..] else {
+        return Err(error(format!("expected rows, player to move and players in {:?}", text)));
+    };
+    let (maximizer, minimizer) = match players.chars().collect::<Vec<char>>()[..] {
+        [max, min] if max != min && valid_symbol(max) && valid_symbol(min) => (max, min),
+        _ => return Err(error(format!("bad players {:?}", players))),
+    };
+    let is_maximizers_turn = match to_move.chars().collect::<Vec<char>>()[..] {
+        [c] if c == maximizer => true,
+        [c] if c == minimizer => false,
+        _ => return Err(error(format!("{:?} is not one of the players", to_move))),
+    };
+
+    let rows: Vec<&str> = rows.split('/').collect();
+    if rows.len() != width {
+        return Err(error(format!("expected {} rows, found {}", width, rows.len())));
+    }
+    let mut cells = Vec::with_capacity(width * width);
+    for row in rows {
+        let start = cells.len();
+        for c in row.chars() {
+            match c.to_digit(10) {
+                Some(empty) => cells.extend((0..empty).map(|_| None)),
+                None if c == maximizer || c == minimizer => cells.push(Some(c)),
+                None => return Err(error(format!("{:?} is not one of the players", c))),
+            }
+        }
+        if cells.len() - start != width {
+            return Err(error(format!("row {:?} is not {} squares", row, width)));
+        }
+    }
+    Ok(Position { cells, maximizer, minimizer, is_maximizers_turn })
+}
+
+/// Reads a move as the player and the square, like `x4`, or just the
+/// square if `player` is given
+pub(super) fn parse_move(
+    text: &str,
+    player: Option<char>,
+    squares: usize
+) -> std::result::Result<(char, usize), NotationError> {
+    let mut chars = text.chars();
+    let (player, square) = match (player, chars.next()) {
+        (_, Some(first)) if valid_symbol(first) => (first, chars.as_str()),
+        (Some(player), _) => (player, text),
+        (None, _) => return Err(error(format!("move {:?} has no player", text))),
+    };
+    match square.parse() {
+        Ok(square) if square < squares => Ok((player, square)),
+        _ => Err(error(format!("bad square in move {:?}", text))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let cells = [Some('x'), None, None, None, Some('o'), None, None, None, None];
+        let text = format(&cells, 3, 'x', 'o', true);
+        assert_eq!(text, "x2/1o1/3 x xo");
+        let position = parse(&text, 3).unwrap();
+        assert_eq!(position.cells, cells);
+        assert!(position.is_maximizers_turn);
+    }
+
+    #[test]
+    fn errors() {
+        assert!(parse("3/3 x xo", 3).is_err());
+        assert!(parse("3/3/4 x xo", 3).is_err());
+        assert!(parse("3/3/2z x xo", 3).is_err());
+        assert!(parse("3/3/3 z xo", 3).is_err());
+        assert!(parse("3/3/3 x xx", 3).is_err());
+        assert!(parse_move("9", Some('x'), 9).is_err());
+        assert!(parse_move("4", None, 9).is_err());
+        assert_eq!(parse_move("4", Some('o'), 9).unwrap(), ('o', 4));
+    }
+}
@@ -1,2 +1,3 @@
+mod fen;
 pub mod four_by_four;
 pub mod three_by_three;
\ No newline at end of file
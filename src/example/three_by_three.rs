@@ -1,5 +1,7 @@
-use crate::{Board, Result};
+use super::fen;
+use crate::{Board, Notation, NotationError, Result};
 use std::fmt::{self, Display};
+use std::str::FromStr;
 
 const WIN_CONDITIONS: [[usize; 3]; 8] = [
     [0, 1, 2],
@@ -12,26 +14,37 @@ const WIN_CONDITIONS: [[usize; 3]; 8] = [
     [2, 4, 6],
 ];
 
-#[derive(Debug, Clone, Copy)]
+/// The number of positions after each of the nine moves of a game
+/// starting from an empty board, for use with [crate::perft_verify].
+/// Until the first game can end after five moves these match the
+/// `9! / (9 - depth)!` ways to fill the board.
+pub const PERFT: [u64; 9] = [9, 72, 504, 3024, 15120, 54720, 148176, 200448, 127872];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TTT {
     maximizer: char,
     minimizer: char,
     pub board: [Option<char>; 9],
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Move {
     pub player: char,
     pub to_position: usize,
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TttResult {
     over: bool,
     score: i64,
 }
 
 impl Result for TttResult {
+    type Score = i64;
+
     fn is_over(&self) -> bool {
         self.over
     }
@@ -82,12 +95,61 @@ impl Display for TTT {
     }
 }
 
+/// Formats the move as the player followed by the square, like `x4`
+impl Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.player, self.to_position)
+    }
+}
+
+impl FromStr for Move {
+    type Err = NotationError;
+
+    fn from_str(text: &str) -> std::result::Result<Move, NotationError> {
+        let (player, to_position) = fen::parse_move(text, None, 9)?;
+        Ok(Move { player, to_position })
+    }
+}
+
+/// Positions are written like `x2/1o1/3 x xo`: the rows from the top
+/// with runs of empty squares as their length, the player to move, and
+/// the maximizer and minimizer. Moves may leave out the player.
+impl Notation for TTT {
+    fn parse_position(text: &str) -> std::result::Result<(TTT, bool), NotationError> {
+        let position = fen::parse(text, 3)?;
+        let mut board = [None; 9];
+        board.copy_from_slice(&position.cells);
+        let game = TTT { maximizer: position.maximizer, minimizer: position.minimizer, board };
+        Ok((game, position.is_maximizers_turn))
+    }
+
+    fn format_position(&self, is_maximizers_turn: bool) -> String {
+        fen::format(&self.board, 3, self.maximizer, self.minimizer, is_maximizers_turn)
+    }
+
+    fn parse_move(&self, text: &str, is_maximizers_turn: bool) -> std::result::Result<Move, NotationError> {
+        let player = if is_maximizers_turn { self.maximizer } else { self.minimizer };
+        let (player, to_position) = fen::parse_move(text, Some(player), 9)?;
+        Ok(Move { player, to_position })
+    }
+
+    fn format_move(&self, game_move: &Move) -> String {
+        game_move.to_string()
+    }
+}
+
 impl Board for TTT {
     type Move = Move;
     type Result = TttResult;
+    type Undo = ();
 
     fn get_valid_moves(&self, is_maximizer: bool) -> Vec<Self::Move> {
         let mut moves: Vec<Self::Move> = Vec::new();
+        self.fill_valid_moves(is_maximizer, &mut moves);
+        moves
+    }
+
+    fn fill_valid_moves(&self, is_maximizer: bool, moves: &mut Vec<Self::Move>) {
         let player = if is_maximizer {
             self.maximizer()
         } else {
@@ -101,14 +163,13 @@ impl Board for TTT {
                 })
             }
         }
-        moves
     }
 
     fn make_move(&mut self, valid_move: &Self::Move) {
         self.board[valid_move.to_position] = Some(valid_move.player);
     }
 
-    fn unmake_move(&mut self, valid_move: &Self::Move) {
+    fn unmake_move(&mut self, valid_move: &Self::Move, _undo: ()) {
         self.board[valid_move.to_position] = None;
     }
 
@@ -183,7 +244,7 @@ mod tests {
       game.unmake_move(&Move {
           player: 'x',
           to_position: 4,
-      });
+      }, ());
       assert_eq!(game.board(), [None; 9]);
   }
 
@@ -250,7 +311,7 @@ mod tests {
       game.unmake_move(&Move {
           player: 'o',
           to_position: 5,
-      });
+      }, ());
       game.make_move(&Move {
           player: 'x',
           to_position: 5,
@@ -261,7 +322,7 @@ mod tests {
       game.unmake_move(&Move {
           player: 'x',
           to_position: 3,
-      });
+      }, ());
       game.make_move(&Move {
           player: 'o',
           to_position: 3,
@@ -1,39 +1,109 @@
+mod book;
+mod checked;
+mod error;
+mod notation;
+mod observer;
+mod perft;
 mod pool;
+mod protocol;
+mod reference;
+mod rng;
+mod score;
+mod search;
+mod selfplay;
+mod stats;
+mod tournament;
+mod tree;
 pub mod example;
+#[cfg(feature = "test-support")]
+pub mod testing;
+
+pub use book::{
+    try_get_best_moves_with_book, Book, BookBuilder, BookMove, OpeningBook, Recommendation,
+};
+pub use checked::{Checked, CheckedUndo, Violation, ViolationKind};
+pub use error::SearchError;
+pub use notation::{Notation, NotationError};
+pub use observer::SearchObserver;
+pub use perft::{perft, perft_divide, perft_divide_multi, perft_multi, perft_verify, PerftMismatch};
+pub use protocol::Protocol;
+pub use rng::Rng;
+pub use score::{Score, Value};
+pub use search::SearchOptions;
+pub use selfplay::{read_samples, write_samples, Sample, SelfPlay};
+pub use stats::{PlyStats, SearchStats, ThreadStats};
+pub use tournament::{play_match, Engine, MatchOptions, MatchResult, Sprt, SprtResult};
+pub use tree::{Tree, TreeNode, TreeRecorder};
 
 use std::cmp::Ordering as cmpOrdering;
 use std::fmt::{Debug, Display};
-use std::sync::{Arc, Mutex, atomic::{AtomicI64, Ordering}};
+use std::sync::{Arc, Mutex, mpsc, atomic::{AtomicI64, Ordering}};
+use std::time::{Duration, Instant};
+use error::panic_message;
+use notation::{format_value, parse_value};
 use pool::ThreadPool;
+use search::{MoveQueue, Searcher};
 use std::thread;
 
 
-pub trait Board: Copy + Send + Display + Debug + 'static {
-    type Move: Copy + Send + Debug + 'static;
+/// Boards only need to be [Clone]; the multi threaded search gives
+/// each of its jobs a clone of the board passed to it.
+pub trait Board: Clone + Send + Display + Debug {
+    type Move: Copy + Send + Debug;
     type Result: Result;
 
+    /// Returned by [Board::make_move] and passed back to
+    /// [Board::unmake_move], e.g. to restore a captured piece or an
+    /// irreversible counter. Boards that can undo a move from the
+    /// move alone can use `()`.
+    type Undo;
+
     /// `make_move` should assume that valid_move will be a valid
     /// move for the current board state
-    fn make_move(&mut self, valid_move: &Self::Move);
+    fn make_move(&mut self, valid_move: &Self::Move) -> Self::Undo;
 
     /// `unmake_move` should assume that made_move is a valid move
     /// that has already been made by calling [Board::make_move]
-    /// with the same move
-    fn unmake_move(&mut self, made_move: &Self::Move);
+    /// with the same move, which returned `undo`
+    fn unmake_move(&mut self, made_move: &Self::Move, undo: Self::Undo);
 
     /// Must return all valid moves for the given player. Returning
     /// invalid moves is a logic error that will cause the engine
     /// to produce invalid results
     fn get_valid_moves(&self, is_maximizer: bool) -> Vec<Self::Move>;
 
+    /// Pushes the same moves as [Board::get_valid_moves] onto `moves`,
+    /// which is empty when called. The search keeps one buffer per
+    /// ply and calls this instead of [Board::get_valid_moves], so
+    /// overriding it avoids allocating a new `Vec` at every node.
+    fn fill_valid_moves(&self, is_maximizer: bool, moves: &mut Vec<Self::Move>) {
+        moves.extend(self.get_valid_moves(is_maximizer));
+    }
+
+    /// For games where a player without valid moves passes the turn
+    /// to the other player, returns the move that passes. It is only
+    /// made when [Board::get_valid_moves] is empty and the game is not
+    /// over, and the search flips the player to move after it as usual.
+    /// When neither player has a valid move the position is scored as if
+    /// the depth limit had been reached. The default returns `None`, in
+    /// which case positions without valid moves are scored the same way
+    /// and the search functions return [SearchError::NoLegalMoves] for
+    /// such a starting position.
+    fn pass_move(&self, _is_maximizer: bool) -> Option<Self::Move> {
+        None
+    }
+
     /// `evaluate` returns a struct that implements the [Result] trait.
     /// The value returned by [Result::score] will be ignored unless
-    /// [Result::is_over] returns true OR the recursive depth
-    /// has been reached.
+    /// the recursive depth has been reached or [Result::is_over]
+    /// returns true and [Result::winner] returns `None`.
     fn evaluate(&self) -> Self::Result;
 }
 
 pub trait Result {
+    /// The type of [Result::score], see [Score]
+    type Score: Score;
+
     /// Should return true if the game is over for any reason
     /// i.e. a player has won or there is a draw
     fn is_over(&self) -> bool;
@@ -41,33 +111,224 @@ pub trait Result {
     /// Returns the score associated with this result. The
     /// maximizing player will seek the maximum score while
     /// the minimizing player will seek the minimum score.
-    fn score(&self) -> i64;
+    fn score(&self) -> Self::Score;
+
+    /// Only called when [Result::is_over] returns true. Should return
+    /// `Some(true)` if the maximizer has won, `Some(false)` if the
+    /// minimizer has won and `None` for a draw. By default a score
+    /// above [Score::ZERO] is a win for the maximizer and a score
+    /// below it is a win for the minimizer.
+    fn winner(&self) -> Option<bool> {
+        match self.score().cmp(&Self::Score::ZERO) {
+            cmpOrdering::Less => Some(false),
+            cmpOrdering::Greater => Some(true),
+            cmpOrdering::Equal => None,
+        }
+    }
 }
 
+type ScoreOf<T> = <<T as Board>::Result as Result>::Score;
+type ValueOf<T> = Value<ScoreOf<T>>;
+
 // struct AlphaBeta {
 //     alpha: i64,
 //     beta: i64
 // }
 
+/// Counters shared by the threads of a search, read with
+/// [Metadata::stats]
 #[derive(Debug)]
 pub struct Metadata {
     moves: AtomicI64,
-    prunes: AtomicI64
+    prunes: AtomicI64,
+    started: Instant,
+    elapsed: Option<Duration>,
+    threads: Mutex<Vec<ThreadStats>>,
 }
 
 impl Metadata {
     pub fn new() -> Metadata {
-        Metadata { moves: AtomicI64::new(0), prunes: AtomicI64::new(0) }
+        Metadata {
+            moves: AtomicI64::new(0),
+            prunes: AtomicI64::new(0),
+            started: Instant::now(),
+            elapsed: None,
+            threads: Mutex::new(vec![]),
+        }
+    }
+
+    /// Takes a snapshot of the counters
+    pub fn stats(&self) -> SearchStats {
+        let threads = self.threads.lock().unwrap().clone();
+        let mut plies = vec![];
+        for thread in &threads {
+            stats::add_plies(&mut plies, &thread.plies);
+        }
+        SearchStats {
+            nodes: self.moves.load(Ordering::Relaxed) as u64,
+            cutoffs: self.prunes.load(Ordering::Relaxed) as u64,
+            elapsed: self.elapsed.unwrap_or_else(|| self.started.elapsed()),
+            max_depth: plies.len() as u16,
+            plies,
+            threads,
+        }
+    }
+
+    /// Adds the counters of a searcher that has finished
+    pub(crate) fn record_thread(&self, thread: ThreadStats) {
+        self.threads.lock().unwrap().push(thread);
+    }
+
+    /// Takes the metadata out of the `Arc` shared with the search
+    /// threads, copying the counters if a thread still holds a clone,
+    /// and stops the clock
+    fn unwrap_shared(metadata: Arc<Metadata>) -> Metadata {
+        let mut metadata = Arc::try_unwrap(metadata).unwrap_or_else(|shared| Metadata {
+            moves: AtomicI64::new(shared.moves.load(Ordering::Relaxed)),
+            prunes: AtomicI64::new(shared.prunes.load(Ordering::Relaxed)),
+            started: shared.started,
+            elapsed: shared.elapsed,
+            threads: Mutex::new(shared.threads.lock().unwrap().clone()),
+        });
+        metadata.elapsed.get_or_insert_with(|| metadata.started.elapsed());
+        metadata
     }
 }
 
+impl Default for Metadata {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The moves returned by a search along with its [Metadata]
+pub type SearchResult<T> = std::result::Result<(Vec<MoveScore<T>>, Metadata), SearchError>;
+
 /// Gets a vector of moves representing all equally good moves for the player
 /// specified by the `is_maximizers_turn` argument.
+///
+/// # Panics
+///
+/// If [try_get_best_moves] would return an error
 pub fn get_best_moves<T: Board>(
+    board: T,
+    max_depth: u16,
+    is_maximizers_turn: bool
+) -> (Vec<MoveScore<T>>, Metadata) {
+    try_get_best_moves(board, max_depth, is_maximizers_turn, &SearchOptions::default())
+        .unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// Same as [get_best_moves], but returns an error instead of panicking
+/// and stops early when one of the `options` limits is reached.
+pub fn try_get_best_moves<T: Board>(
+    board: T,
+    max_depth: u16,
+    is_maximizers_turn: bool,
+    options: &SearchOptions
+) -> SearchResult<T> {
+    search_single(board, max_depth, is_maximizers_turn, options)
+}
+
+/// Same as [try_get_best_moves], but visits every position up to
+/// `max_depth` without alpha-beta pruning. This is much slower and is
+/// meant as a reference to check the pruned searches against, see
+/// [get_best_moves_cross_checked]. It is a separate, deliberately simple
+/// search sharing no code with the pruned ones.
+pub fn try_get_best_moves_minimax<T: Board>(
     mut board: T,
     mut max_depth: u16,
+    is_maximizers_turn: bool,
+    options: &SearchOptions
+) -> SearchResult<T> {
+    if max_depth == 0 {
+        max_depth = u16::MAX
+    }
+
+    let metadata = Arc::new(Metadata::new());
+
+    if board.evaluate().is_over() {
+        return Ok((vec![], Metadata::unwrap_shared(metadata)));
+    }
+
+    let starting_moves = starting_moves(&board, is_maximizers_turn)?;
+
+    options.observe(|observer| observer.iteration_started(max_depth));
+    let moves = reference::root_values(
+        &mut board,
+        &starting_moves,
+        max_depth,
+        is_maximizers_turn,
+        &metadata,
+        options
+    )?;
+
+    finish(moves, metadata, max_depth, is_maximizers_turn, options)
+}
+
+/// Searches `board` with [try_get_best_moves] and with
+/// [try_get_best_moves_minimax], checking that both give every starting
+/// move the same value. Starting moves are searched with a full window,
+/// so pruning must not change their values.
+///
+/// # Panics
+///
+/// If the searches disagree, with the position and the value each gave
+/// to the first starting move they disagree on, or if either search
+/// returns an error.
+pub fn get_best_moves_cross_checked<T: Board>(
+    board: T,
+    mut max_depth: u16,
     is_maximizers_turn: bool
 ) -> (Vec<MoveScore<T>>, Metadata) {
+    if max_depth == 0 {
+        max_depth = u16::MAX
+    }
+    if board.evaluate().is_over() {
+        return (vec![], Metadata::unwrap_shared(Arc::new(Metadata::new())));
+    }
+    let starting_moves = starting_moves(&board, is_maximizers_turn).unwrap_or_else(|e| panic!("{}", e));
+
+    let metadata = Arc::new(Metadata::new());
+    let options = SearchOptions::default();
+    let mut searcher = Searcher::new(max_depth, Arc::clone(&metadata), options.clone());
+    let mut searched = board.clone();
+    let mut pruned = Vec::with_capacity(starting_moves.len());
+    for m in &starting_moves {
+        let score = searcher
+            .search_move(&mut searched, m, is_maximizers_turn)
+            .unwrap_or_else(|e| panic!("{}", e));
+        let best = searcher.record(&mut pruned, *m, score, is_maximizers_turn);
+        searcher.report(m, &score, best);
+    }
+    drop(searcher);
+    let unpruned = reference::root_values(
+        &mut searched,
+        &starting_moves,
+        max_depth,
+        is_maximizers_turn,
+        &Metadata::new(),
+        &options
+    )
+    .unwrap_or_else(|e| panic!("{}", e));
+
+    for (alphabeta, minimax) in pruned.iter().zip(&unpruned) {
+        assert!(
+            alphabeta.score == minimax.score,
+            "alpha-beta gave {:?} a value of {:?} but minimax gave {:?} in position {}",
+            alphabeta.game_move, alphabeta.score, minimax.score, board
+        );
+    }
+    (best_moves(pruned, is_maximizers_turn), Metadata::unwrap_shared(metadata))
+}
+
+/// Runs a single threaded alpha-beta search
+fn search_single<T: Board>(
+    mut board: T,
+    mut max_depth: u16,
+    is_maximizers_turn: bool,
+    options: &SearchOptions
+) -> SearchResult<T> {
 
     if max_depth == 0 {
         max_depth = u16::MAX
@@ -76,60 +337,76 @@ pub fn get_best_moves<T: Board>(
     let metadata = Arc::new(Metadata::new());
 
     if board.evaluate().is_over() {
-        return (vec![], Arc::try_unwrap(metadata).unwrap());
+        return Ok((vec![], Metadata::unwrap_shared(metadata)));
     }
 
-    let mut moves: Vec<MoveScore<T>> = board
-        .get_valid_moves(is_maximizers_turn)
-        .into_iter()
-        .map(|m| {
-            board.make_move(&m);
-            let metadata = Arc::clone(&metadata);
-            let score = alphabeta(
-                &mut board,
-                0,
-                max_depth,
-                i64::MIN,
-                i64::MAX,
-                !is_maximizers_turn,
-                metadata
-            );
-            board.unmake_move(&m);
-            MoveScore {
-                game_move: m,
-                score,
-            }
-        })
-        .collect();
+    let starting_moves = starting_moves(&board, is_maximizers_turn)?;
 
-    moves.sort_by(|a, b| {
-        if is_maximizers_turn {
-            b.score.partial_cmp(&a.score).unwrap()
-        } else {
-            a.score.partial_cmp(&b.score).unwrap()
-        }
-    });
+    options.observe(|observer| observer.iteration_started(max_depth));
+    let mut searcher = Searcher::new(max_depth, Arc::clone(&metadata), options.clone());
+    let mut moves = Vec::with_capacity(starting_moves.len());
+    for m in starting_moves {
+        let score = searcher.search_move(&mut board, &m, is_maximizers_turn)?;
+        let best = searcher.record(&mut moves, m, score, is_maximizers_turn);
+        searcher.report(&m, &score, best);
+    }
+    drop(searcher);
 
-    let high_score = moves[0].score;
+    finish(moves, metadata, max_depth, is_maximizers_turn, options)
+}
 
-    (moves
+/// Same as [try_get_best_moves], but searches a [Checked] copy of the
+/// board and returns [SearchError::InvalidBoard] if the [Board]
+/// implementation was found to be inconsistent.
+pub fn try_get_best_moves_checked<T>(
+    board: T,
+    max_depth: u16,
+    is_maximizers_turn: bool,
+    options: &SearchOptions
+) -> SearchResult<T>
+where
+    T: Board + PartialEq,
+    T::Move: PartialEq,
+{
+    let checked = Checked::new(board);
+    let searched = try_get_best_moves(checked.clone(), max_depth, is_maximizers_turn, options);
+    // a broken board can cause other errors, so report it first
+    if let Some(violation) = checked.violation() {
+        return Err(violation.into());
+    }
+    let (moves, metadata) = searched?;
+    let moves = moves
         .into_iter()
-        .filter_map(|m| {
-            if m.score == high_score {
-                Some(m)
-            } else {
-                None
-            }
-        })
-        .collect(), Arc::try_unwrap(metadata).unwrap())
+        .map(|m| MoveScore { game_move: m.game_move, score: m.score, pv: m.pv })
+        .collect();
+    Ok((moves, metadata))
 }
 
-pub fn get_best_moves_multi<T: Board>(
-    mut board: T,
-    mut max_depth: u16,
+/// Same as [get_best_moves], but the starting moves are searched by a
+/// pool of `threads` threads, or one per cpu if `threads` is 0.
+///
+/// # Panics
+///
+/// If [try_get_best_moves_multi] would return an error
+pub fn get_best_moves_multi<T: Board + 'static>(
+    board: T,
+    max_depth: u16,
     is_maximizers_turn: bool,
     threads: usize
 ) -> (Vec<MoveScore<T>>, Metadata) {
+    try_get_best_moves_multi(board, max_depth, is_maximizers_turn, threads, &SearchOptions::default())
+        .unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// Same as [get_best_moves_multi], but returns an error instead of
+/// panicking and stops early when one of the `options` limits is reached.
+pub fn try_get_best_moves_multi<T: Board + 'static>(
+    board: T,
+    mut max_depth: u16,
+    is_maximizers_turn: bool,
+    threads: usize,
+    options: &SearchOptions
+) -> SearchResult<T> {
 
 
     if max_depth == 0 {
@@ -139,56 +416,184 @@ pub fn get_best_moves_multi<T: Board>(
     let metadata = Arc::new(Metadata::new());
 
     if board.evaluate().is_over() {
-        return (vec![], Arc::try_unwrap(metadata).unwrap());
+        return Ok((vec![], Metadata::unwrap_shared(metadata)));
     }
 
-    let main = Arc::new(thread::current());
-    let pool = ThreadPool::new(threads, main);
-    let starting_moves = board.get_valid_moves(is_maximizers_turn);
-    let starting_moves_len = starting_moves.len();
+    let starting_moves = starting_moves(&board, is_maximizers_turn)?;
+
+    options.observe(|observer| observer.iteration_started(max_depth));
+    let pool = ThreadPool::new(threads);
+    let jobs = pool.size().min(starting_moves.len());
+    let queue = Arc::new(MoveQueue::new(starting_moves));
     let moves: Arc<Mutex<Vec<MoveScore<T>>>> = Arc::new(Mutex::new(vec![]));
-    for m in starting_moves {
-        board.make_move(&m);
+    let (tx, rx) = mpsc::channel();
+    // one job per thread, each reusing its searcher's move stack for
+    // every starting move it takes from the queue
+    for job in 0..jobs {
+        let mut board = board.clone();
         let metadata = Arc::clone(&metadata);
+        let options = options.clone();
+        let queue = Arc::clone(&queue);
         let moves = Arc::clone(&moves);
-        pool.execute(move || { 
-            let score = alphabeta(
-                &mut board,
-                0,
-                max_depth,
-                i64::MIN,
-                i64::MAX,
-                !is_maximizers_turn,
-                metadata
-            );
-            moves.lock().unwrap().push(MoveScore { game_move: m, score });
-        });
-        board.unmake_move(&m);
+        options.observe(|observer| observer.job_dispatched(job));
+        pool.execute(move || {
+            Searcher::new(max_depth, metadata, options)
+                .search_queue(&mut board, &queue, &moves, is_maximizers_turn)
+        }, tx.clone())?;
     }
+    drop(tx);
 
-    {
-        let moves = Arc::clone(&moves);
-        while moves.lock().unwrap().len() < starting_moves_len {
-            thread::park()
-        }
+    let searched: Vec<_> = (0..jobs)
+        .map(|_| rx.recv().map_err(|_| SearchError::PoolShutdown).and_then(|job| job?))
+        .collect();
+    first_failure(searched)?;
+
+    let moves = std::mem::take(&mut *moves.lock().unwrap());
+
+    finish(moves, metadata, max_depth, is_maximizers_turn, options)
+}
+
+/// Same as [get_best_moves_multi], but runs on scoped threads instead of
+/// a thread pool. Neither the board nor its moves need to be `'static`,
+/// so boards may borrow shared data such as rule tables.
+///
+/// # Panics
+///
+/// If [try_get_best_moves_scoped] would return an error
+pub fn get_best_moves_scoped<T: Board>(
+    board: T,
+    max_depth: u16,
+    is_maximizers_turn: bool,
+    threads: usize
+) -> (Vec<MoveScore<T>>, Metadata) {
+    try_get_best_moves_scoped(board, max_depth, is_maximizers_turn, threads, &SearchOptions::default())
+        .unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// Same as [get_best_moves_scoped], but returns an error instead of
+/// panicking and stops early when one of the `options` limits is reached.
+pub fn try_get_best_moves_scoped<T: Board>(
+    board: T,
+    mut max_depth: u16,
+    is_maximizers_turn: bool,
+    mut threads: usize,
+    options: &SearchOptions
+) -> SearchResult<T> {
+
+    if max_depth == 0 {
+        max_depth = u16::MAX
+    }
+
+    if threads == 0 {
+        threads = num_cpus::get();
     }
 
-    let mut moves = moves.lock().unwrap().clone();
+    let metadata = Arc::new(Metadata::new());
 
+    if board.evaluate().is_over() {
+        return Ok((vec![], Metadata::unwrap_shared(metadata)));
+    }
+
+    let starting_moves = starting_moves(&board, is_maximizers_turn)?;
+
+    options.observe(|observer| observer.iteration_started(max_depth));
+    let threads = threads.min(starting_moves.len());
+    let queue = MoveQueue::new(starting_moves);
+    let moves: Mutex<Vec<MoveScore<T>>> = Mutex::new(vec![]);
+    thread::scope(|scope| {
+        let handles: Vec<_> = (0..threads)
+            .map(|job| {
+                options.observe(|observer| observer.job_dispatched(job));
+                let mut board = board.clone();
+                let mut searcher = Searcher::new(max_depth, Arc::clone(&metadata), options.clone());
+                let (queue, moves) = (&queue, &moves);
+                scope.spawn(move || {
+                    searcher.search_queue(&mut board, queue, moves, is_maximizers_turn)
+                })
+            })
+            .collect();
+        // join every thread before returning an error, otherwise the
+        // scope panics for threads that panicked and were not joined
+        let searched: Vec<_> = handles
+            .into_iter()
+            .map(|handle| handle
+                .join()
+                .map_err(|payload| SearchError::WorkerPanicked(panic_message(payload)))?)
+            .collect();
+        first_failure(searched)
+    })?;
+
+    let moves = moves.into_inner().unwrap();
+
+    finish(moves, metadata, max_depth, is_maximizers_turn, options)
+}
+
+/// Picks the error to return from the threads of a search. When one
+/// thread fails the others are halted and return
+/// [SearchError::Cancelled], so any other error is the cause.
+fn first_failure(
+    searched: Vec<std::result::Result<(), SearchError>>
+) -> std::result::Result<(), SearchError> {
+    let mut errors: Vec<_> = searched.into_iter().filter_map(|searched| searched.err()).collect();
+    match errors.iter().position(|e| *e != SearchError::Cancelled) {
+        Some(cause) => Err(errors.swap_remove(cause)),
+        None => errors.into_iter().next().map_or(Ok(()), Err),
+    }
+}
+
+/// Stops the clock and tells the observer a search has finished
+fn finish<T: Board>(
+    moves: Vec<MoveScore<T>>,
+    metadata: Arc<Metadata>,
+    max_depth: u16,
+    is_maximizers_turn: bool,
+    options: &SearchOptions
+) -> SearchResult<T> {
+    let metadata = Metadata::unwrap_shared(metadata);
+    options.observe(|observer| observer.iteration_finished(max_depth, &metadata.stats()));
+    Ok((best_moves(moves, is_maximizers_turn), metadata))
+}
+
+/// Gets the valid moves for the player to move, or a single pass if
+/// they have none and the game allows passing
+pub(crate) fn moves_or_pass<T: Board>(board: &T, is_maximizers_turn: bool) -> Vec<T::Move> {
+    let moves = board.get_valid_moves(is_maximizers_turn);
+    if moves.is_empty() {
+        board.pass_move(is_maximizers_turn).into_iter().collect()
+    } else {
+        moves
+    }
+}
+
+/// Gets the moves to search from the root
+fn starting_moves<T: Board>(
+    board: &T,
+    is_maximizers_turn: bool
+) -> std::result::Result<Vec<T::Move>, SearchError> {
+    let moves = moves_or_pass(board, is_maximizers_turn);
+    if moves.is_empty() {
+        Err(SearchError::NoLegalMoves)
+    } else {
+        Ok(moves)
+    }
+}
+
+/// Keeps only the moves that share the best score for the player to move
+fn best_moves<T: Board>(
+    mut moves: Vec<MoveScore<T>>,
+    is_maximizers_turn: bool
+) -> Vec<MoveScore<T>> {
     moves.sort_by(|a, b| {
         if is_maximizers_turn {
-            b.score.partial_cmp(&a.score).unwrap()
+            b.score.cmp(&a.score)
         } else {
-            a.score.partial_cmp(&b.score).unwrap()
+            a.score.cmp(&b.score)
         }
     });
 
-    // println!("final scores:");
-    // moves.iter().for_each(|m| {println!("{:?}", m);});
-
     let high_score = moves[0].score;
 
-    (moves
+    moves
         .into_iter()
         .filter_map(|m| {
             if m.score == high_score {
@@ -197,82 +602,24 @@ pub fn get_best_moves_multi<T: Board>(
                 None
             }
         })
-        .collect(), Arc::try_unwrap(metadata).unwrap())
-    
+        .collect()
 }
 
-fn alphabeta<T: Board>(
-    board: &mut T,
-    depth: u16,
-    max_depth: u16,
-    mut alpha: i64,
-    mut beta: i64,
-    is_max: bool,
-    metadata: Arc<Metadata>
-) -> i64 {
-    let result = board.evaluate();
-    let mut score = result.score();
-    {
-        metadata.moves.fetch_add(1, Ordering::Relaxed);
-    }
-    if depth == max_depth || result.is_over() {
-        return match score.cmp(&0) {
-            cmpOrdering::Less => score + (depth as i64),
-            cmpOrdering::Greater => score - (depth as i64),
-            cmpOrdering::Equal => score,
-        };
-    }
-
-    let moves = board.get_valid_moves(is_max);
-
-    if is_max {
-        score = i64::MIN;
-        for m in moves {
-            board.make_move(&m);
-            score = score.max(alphabeta(
-                board,
-                depth + 1,
-                max_depth,
-                alpha,
-                beta,
-                false,
-                Arc::clone(&metadata)
-            ));
-            board.unmake_move(&m);
-            alpha = alpha.max(score);
-            if score >= beta {
-                metadata.prunes.fetch_add(1, Ordering::Relaxed);
-                break;
-            }
-        }
-        score
-    } else {
-        score = i64::MAX;
-        for m in moves {
-            board.make_move(&m);
-            score = score.min(alphabeta(board,
-                depth + 1,
-                max_depth,
-                alpha,
-                beta,
-                true,
-                Arc::clone(&metadata)
-            ));
-            board.unmake_move(&m);
-            beta = beta.min(score);
-            if score <= alpha {
-                metadata.prunes.fetch_add(1, Ordering::Relaxed);
-                break;
-            }
-        }
-        score
-    }
-}
-
-
-
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "T::Move: serde::Serialize, ValueOf<T>: serde::Serialize",
+        deserialize = "T::Move: serde::Deserialize<'de>, ValueOf<T>: serde::Deserialize<'de>"
+    ))
+)]
 pub struct MoveScore<T: Board> {
     pub game_move: <T as Board>::Move,
-    pub score: i64,
-}
\ No newline at end of file
+    /// Use [Value::win_in] and [Value::loss_in] to find out how
+    /// many plies a forced win or loss is from the current position
+    pub score: ValueOf<T>,
+    /// The moves both players are expected to play, starting with
+    /// `game_move`
+    pub pv: Vec<<T as Board>::Move>,
+}
@@ -0,0 +1,60 @@
+use crate::{Board, ScoreOf, Value, ValueOf};
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+/// Returned when text can not be read as a position or move
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NotationError(pub String);
+
+impl Display for NotationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl Error for NotationError {}
+
+/// Converts positions and moves to and from text, so they can be sent
+/// over a [crate::Protocol] or written to files. Formatting and then
+/// parsing must give back the same position or move.
+pub trait Notation: Board {
+    /// Reads a position along with whether it is the maximizer's turn
+    fn parse_position(text: &str) -> std::result::Result<(Self, bool), NotationError>;
+
+    fn format_position(&self, is_maximizers_turn: bool) -> String;
+
+    /// Reads a move for the player to move. The move does not need to be
+    /// valid, the caller checks that.
+    fn parse_move(
+        &self,
+        text: &str,
+        is_maximizers_turn: bool
+    ) -> std::result::Result<Self::Move, NotationError>;
+
+    fn format_move(&self, game_move: &Self::Move) -> String;
+}
+
+/// Writes a value as its score, or as `win <plies>` or `loss <plies>`
+/// for a forced result
+pub(crate) fn format_value<T: Board>(value: ValueOf<T>) -> String {
+    match value {
+        Value::Win(plies) => format!("win {}", plies),
+        Value::Loss(plies) => format!("loss {}", plies),
+        Value::Score(score) => format!("{:?}", score),
+    }
+}
+
+/// Reads a value written by [format_value]
+pub(crate) fn parse_value<T: Board>(text: &str) -> std::result::Result<ValueOf<T>, NotationError>
+where
+    ScoreOf<T>: FromStr,
+{
+    let error = || NotationError(format!("bad score {:?}", text));
+    let plies = |plies: &str| plies.parse().map_err(|_| error());
+    match text.split_once(' ') {
+        Some(("win", rest)) => Ok(Value::Win(plies(rest)?)),
+        Some(("loss", rest)) => Ok(Value::Loss(plies(rest)?)),
+        _ => text.parse().map(Value::Score).map_err(|_| error()),
+    }
+}
@@ -0,0 +1,41 @@
+use crate::SearchStats;
+use std::fmt::Debug;
+
+/// Receives events from a search, set with [crate::SearchOptions::observer].
+/// Every method does nothing by default. Multi threaded searches call
+/// the observer from all of their threads, so it should be quick and
+/// must be [Sync]. Moves and scores are passed as [Debug] so observers
+/// work with any [crate::Board].
+pub trait SearchObserver: Send + Sync {
+    /// A search to `depth` plies has found its starting moves and is
+    /// about to search them
+    fn iteration_started(&self, _depth: u16) {}
+
+    /// A search to `depth` plies finished without an error
+    fn iteration_finished(&self, _depth: u16, _stats: &SearchStats) {}
+
+    /// A starting move scored better than every starting move searched
+    /// before it
+    fn best_move_changed(&self, _game_move: &dyn Debug, _score: &dyn Debug) {}
+
+    /// A starting move has been searched
+    fn root_move_searched(&self, _game_move: &dyn Debug, _score: &dyn Debug) {}
+
+    /// A position `depth` plies below a starting move was cut off after
+    /// `searched` of its moves. Called very often, so keep it cheap.
+    fn cutoff(&self, _depth: u16, _searched: usize) {}
+
+    /// The search is about to make `game_move`, leading to a position
+    /// `depth` plies below a starting move, with the window `alpha` to
+    /// `beta`. Starting moves are entered with `depth` 0. Called for
+    /// every move, so keep it cheap.
+    fn move_entered(&self, _depth: u16, _game_move: &dyn Debug, _alpha: &dyn Debug, _beta: &dyn Debug) {}
+
+    /// The search took back `game_move` after finding the `score` of
+    /// the position it led to, or after stopping early if there is no
+    /// score. Each thread exits moves in the reverse order it entered them.
+    fn move_exited(&self, _depth: u16, _game_move: &dyn Debug, _score: Option<&dyn Debug>) {}
+
+    /// A multi threaded search handed job number `job` to a thread
+    fn job_dispatched(&self, _job: usize) {}
+}
@@ -0,0 +1,134 @@
+use crate::pool::ThreadPool;
+use crate::{moves_or_pass, Board, Result, SearchError};
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::sync::mpsc;
+
+/// Counts the positions exactly `depth` plies from `board`, which is
+/// useful for checking move generation against known counts. Games that
+/// end sooner are not counted, and forced passes count as moves.
+pub fn perft<T: Board>(mut board: T, depth: u16, is_maximizers_turn: bool) -> u64 {
+    count(&mut board, depth, is_maximizers_turn)
+}
+
+/// Same as [perft], but lists the count for each starting move
+pub fn perft_divide<T: Board>(
+    mut board: T,
+    depth: u16,
+    is_maximizers_turn: bool
+) -> Vec<(T::Move, u64)> {
+    if depth == 0 || board.evaluate().is_over() {
+        return vec![];
+    }
+    moves_or_pass(&board, is_maximizers_turn)
+        .into_iter()
+        .map(|m| {
+            let undo = board.make_move(&m);
+            let nodes = count(&mut board, depth - 1, !is_maximizers_turn);
+            board.unmake_move(&m, undo);
+            (m, nodes)
+        })
+        .collect()
+}
+
+/// Same as [perft], but each starting move is counted by a pool of
+/// `threads` threads, or one per cpu if `threads` is 0.
+pub fn perft_multi<T: Board + 'static>(
+    board: T,
+    depth: u16,
+    is_maximizers_turn: bool,
+    threads: usize
+) -> std::result::Result<u64, SearchError> {
+    if depth == 0 {
+        return Ok(1);
+    }
+    let divided = perft_divide_multi(board, depth, is_maximizers_turn, threads)?;
+    Ok(divided.into_iter().map(|(_, nodes)| nodes).sum())
+}
+
+/// Same as [perft_divide], but each starting move is counted by a pool
+/// of `threads` threads, or one per cpu if `threads` is 0.
+pub fn perft_divide_multi<T: Board + 'static>(
+    board: T,
+    depth: u16,
+    is_maximizers_turn: bool,
+    threads: usize
+) -> std::result::Result<Vec<(T::Move, u64)>, SearchError> {
+    if depth == 0 || board.evaluate().is_over() {
+        return Ok(vec![]);
+    }
+    let starting_moves = moves_or_pass(&board, is_maximizers_turn);
+    let pool = ThreadPool::new(threads);
+    let (tx, rx) = mpsc::channel();
+    for (i, m) in starting_moves.iter().enumerate() {
+        let mut board = board.clone();
+        let m = *m;
+        pool.execute(move || {
+            board.make_move(&m);
+            (i, count(&mut board, depth - 1, !is_maximizers_turn))
+        }, tx.clone())?;
+    }
+    drop(tx);
+
+    let mut counts = vec![0; starting_moves.len()];
+    for _ in 0..starting_moves.len() {
+        let (i, nodes) = rx.recv().map_err(|_| SearchError::PoolShutdown)??;
+        counts[i] = nodes;
+    }
+    Ok(starting_moves.into_iter().zip(counts).collect())
+}
+
+/// Returned by [perft_verify] for the first depth with the wrong count
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PerftMismatch {
+    pub depth: u16,
+    pub expected: u64,
+    pub found: u64,
+}
+
+impl Display for PerftMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "expected {} positions at depth {}, found {}", self.expected, self.depth, self.found)
+    }
+}
+
+impl Error for PerftMismatch {}
+
+/// Compares [perft] at each depth against `expected`, which starts at
+/// depth 1. See [crate::example::three_by_three::PERFT] for an example.
+pub fn perft_verify<T: Board>(
+    mut board: T,
+    is_maximizers_turn: bool,
+    expected: &[u64]
+) -> std::result::Result<(), PerftMismatch> {
+    for (depth, expected) in (1..).zip(expected) {
+        let found = count(&mut board, depth, is_maximizers_turn);
+        if found != *expected {
+            return Err(PerftMismatch { depth, expected: *expected, found });
+        }
+    }
+    Ok(())
+}
+
+fn count<T: Board>(board: &mut T, depth: u16, is_maximizers_turn: bool) -> u64 {
+    if depth == 0 {
+        return 1;
+    }
+    if board.evaluate().is_over() {
+        return 0;
+    }
+    let moves = moves_or_pass(board, is_maximizers_turn);
+    // every position one ply away is counted, even if the game is over
+    if depth == 1 {
+        return moves.len() as u64;
+    }
+    moves
+        .into_iter()
+        .map(|m| {
+            let undo = board.make_move(&m);
+            let nodes = count(board, depth - 1, !is_maximizers_turn);
+            board.unmake_move(&m, undo);
+            nodes
+        })
+        .sum()
+}
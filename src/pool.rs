@@ -1,9 +1,13 @@
+use crate::SearchError;
+use crate::error::panic_message;
+use std::panic::{self, AssertUnwindSafe};
 use std::sync::{Arc, Mutex};
-use std::thread::{self, Thread};
-use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use std::sync::mpsc::{self, Receiver, Sender};
 
 pub struct ThreadPool {
-    tx: mpsc::Sender<Job>
+    tx: Option<mpsc::Sender<Job>>,
+    workers: Vec<Worker>
 }
 
 type Job = Box<dyn FnOnce() + Send + 'static>;
@@ -11,33 +15,99 @@ type Job = Box<dyn FnOnce() + Send + 'static>;
 struct Worker(thread::JoinHandle<()>);
 
 impl Worker {
-    fn new(id: usize, rx: Arc<Mutex<Receiver<Job>>>, main: Arc<Thread>) -> Worker {
+    fn new(rx: Arc<Mutex<Receiver<Job>>>) -> Worker {
         Worker (thread::spawn(move || loop {
             let job = rx.lock().unwrap().recv();
-            if job.is_ok() {
-                println!("thread {} taking job", id);
-                job.unwrap()();
-                main.unpark();
+            match job {
+                Ok(job) => job(),
+                Err(_) => break
             }
         }))
     }
 }
 
 impl ThreadPool {
-    pub fn new(mut size: usize, main: Arc<Thread>) -> ThreadPool {
+    pub fn new(mut size: usize) -> ThreadPool {
         if size == 0 { size = num_cpus::get(); }
         let (tx, rx) = mpsc::channel();
         let rx = Arc::new(Mutex::new(rx));
-        for i in 0..size {
-            Worker::new(i, Arc::clone(&rx), Arc::clone(&main));
-        }
-        ThreadPool { tx }
+        let workers = (0..size)
+            .map(|_| Worker::new(Arc::clone(&rx)))
+            .collect();
+        ThreadPool { tx: Some(tx), workers }
+    }
+
+    pub fn size(&self) -> usize {
+        self.workers.len()
     }
 
-    pub fn execute<F>(&self, f: F)
+    /// Runs `f` on one of the workers and sends what it returns to
+    /// `done`. If `f` panics the panic is caught, so the worker stays
+    /// available for other jobs, and its message is sent instead.
+    pub fn execute<F, R>(&self, f: F, done: Sender<Result<R, SearchError>>) -> Result<(), SearchError>
     where
-        F: FnOnce() + Send + 'static,
+        F: FnOnce() -> R + Send + 'static,
+        R: Send + 'static,
         {
-            self.tx.send(Box::new(f)).unwrap();
+            let job = move || {
+                let outcome = panic::catch_unwind(AssertUnwindSafe(f))
+                    .map_err(|payload| SearchError::WorkerPanicked(panic_message(payload)));
+                // the receiver is only gone if the caller stopped waiting
+                let _ = done.send(outcome);
+            };
+            self.tx
+                .as_ref()
+                .ok_or(SearchError::PoolShutdown)?
+                .send(Box::new(job))
+                .map_err(|_| SearchError::PoolShutdown)
+        }
+}
+
+/// Closing the channel lets each worker finish the jobs already queued
+/// and then leave its loop, so dropping the pool waits for every job
+/// and no worker threads outlive it
+impl Drop for ThreadPool {
+    fn drop(&mut self) {
+        drop(self.tx.take());
+        for worker in self.workers.drain(..) {
+            worker.0.join().unwrap();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::ThreadPool;
+    use crate::SearchError;
+    use std::sync::mpsc;
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn survives_panic() {
+        let pool = ThreadPool::new(1);
+        let (tx, rx) = mpsc::channel();
+        pool.execute(|| panic!("job panicked"), tx.clone()).unwrap();
+        pool.execute(|| 5, tx).unwrap();
+        assert_eq!(
+            rx.recv().unwrap(),
+            Err(SearchError::WorkerPanicked(String::from("job panicked")))
+        );
+        assert_eq!(rx.recv().unwrap(), Ok(5));
+    }
+
+    #[test]
+    fn drop_waits_for_jobs() {
+        let pool = ThreadPool::new(2);
+        let (tx, rx) = mpsc::channel();
+        for i in 0..4 {
+            pool.execute(move || {
+                thread::sleep(Duration::from_millis(10));
+                i
+            }, tx.clone()).unwrap();
         }
-}
\ No newline at end of file
+        drop(pool);
+        assert_eq!(rx.try_iter().count(), 4);
+    }
+}
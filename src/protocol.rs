@@ -0,0 +1,261 @@
+use crate::{
+    format_value, moves_or_pass, try_get_best_moves_multi, Notation,
+    NotationError, Result, SearchOptions,
+};
+use std::io::{self, BufRead, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// Limits of a `go` command, unlimited if not given
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct Limits {
+    depth: Option<u16>,
+    movetime: Option<Duration>,
+    nodes: Option<u64>,
+}
+
+/// Runs the engine behind a line based text protocol in the style of
+/// UCI, for GUIs and tournament managers. Commands are read one per
+/// line:
+///
+/// * `position startpos [moves <move>...]` or
+///   `position <position> [moves <move>...]` sets up the position to
+///   search, in the [Notation] of the board
+/// * `go [depth <depth>] [movetime <ms>] [nodes <count>]` searches the
+///   position one ply deeper at a time until a limit is reached, in the
+///   background. Depths are the `max_depth` of [crate::get_best_moves],
+///   starting from 1.
+/// * `stop` ends the search early
+/// * `isready` is answered with `readyok` right away, even while a
+///   search is running
+/// * `quit` stops any search and returns
+///
+/// Each finished iteration of a search is reported with a line like
+/// `info depth 3 score 0 nodes 1024 time 5 nps 204800 pv 4 0 8`, where
+/// scores are from the maximizer's point of view and forced results are
+/// shown as `win <plies>` or `loss <plies>`. A search always ends with
+/// `bestmove <move>`, or `bestmove none` if the game is over, even if
+/// the board panics during the search. A new `position` or `go` waits
+/// for the running search, so send `stop` first to end an unlimited
+/// one. Problems with a command are reported as `info string <message>`.
+pub struct Protocol<T: Notation> {
+    start: T,
+    start_is_max: bool,
+    board: T,
+    is_max: bool,
+    threads: usize,
+    search: Option<(JoinHandle<()>, Arc<AtomicBool>)>,
+}
+
+impl<T> Protocol<T>
+where
+    T: Notation + 'static,
+    T::Move: PartialEq,
+{
+    /// `start` is the position for `position startpos`. Searches use a
+    /// pool of `threads` threads, or one per cpu if `threads` is 0.
+    pub fn new(start: T, is_maximizers_turn: bool, threads: usize) -> Protocol<T> {
+        Protocol {
+            board: start.clone(),
+            is_max: is_maximizers_turn,
+            start,
+            start_is_max: is_maximizers_turn,
+            threads,
+            search: None,
+        }
+    }
+
+    /// Reads commands from `input` until `quit` or the end of the input,
+    /// writing responses to `output`. At the end of the input a running
+    /// search is finished first.
+    pub fn run(
+        &mut self,
+        input: impl BufRead,
+        output: impl Write + Send + 'static
+    ) -> io::Result<()> {
+        let output = Arc::new(Mutex::new(output));
+        for line in input.lines() {
+            let line = line?;
+            let mut words = line.split_whitespace();
+            match words.next() {
+                None => {}
+                Some("position") => {
+                    self.wait();
+                    if let Err(e) = self.set_position(words.collect()) {
+                        send(&output, &format!("info string {}", e))?;
+                    }
+                }
+                Some("go") => {
+                    self.wait();
+                    match parse_limits(words) {
+                        Ok(limits) => self.go(limits, &output),
+                        Err(e) => send(&output, &format!("info string {}", e))?,
+                    }
+                }
+                Some("stop") => self.stop(),
+                Some("isready") => send(&output, "readyok")?,
+                Some("quit") => {
+                    self.stop();
+                    break;
+                }
+                Some(other) => send(&output, &format!("info string unknown command {}", other))?,
+            }
+        }
+        self.wait();
+        Ok(())
+    }
+
+    fn set_position(&mut self, words: Vec<&str>) -> std::result::Result<(), NotationError> {
+        let split = words.iter().position(|word| *word == "moves").unwrap_or(words.len());
+        let (position, moves) = words.split_at(split);
+        let (mut board, mut is_max) = match position {
+            ["startpos"] => (self.start.clone(), self.start_is_max),
+            [] => return Err(NotationError(String::from("missing position"))),
+            _ => T::parse_position(&position.join(" "))?,
+        };
+        for text in moves.iter().skip(1) {
+            let m = board.parse_move(text, is_max)?;
+            if board.evaluate().is_over() || !moves_or_pass(&board, is_max).contains(&m) {
+                return Err(NotationError(format!("illegal move {}", text)));
+            }
+            board.make_move(&m);
+            is_max = !is_max;
+        }
+        self.board = board;
+        self.is_max = is_max;
+        Ok(())
+    }
+
+    fn go<W: Write + Send + 'static>(&mut self, limits: Limits, output: &Arc<Mutex<W>>) {
+        let stop = Arc::new(AtomicBool::new(false));
+        let (board, is_max, threads) = (self.board.clone(), self.is_max, self.threads);
+        let (output, stopped) = (Arc::clone(output), Arc::clone(&stop));
+        let handle = thread::spawn(move || {
+            // there is nobody left to tell if the output is gone
+            let _ = search(board, is_max, threads, limits, stopped, &output);
+        });
+        self.search = Some((handle, stop));
+    }
+
+    fn stop(&mut self) {
+        if let Some((_, stop)) = &self.search {
+            stop.store(true, Ordering::Relaxed);
+        }
+        self.wait();
+    }
+
+    fn wait(&mut self) {
+        if let Some((handle, _)) = self.search.take() {
+            // a panicking search has already been reported by the panic
+            // hook and has sent its best move while unwinding
+            let _ = handle.join();
+        }
+    }
+}
+
+fn send<W: Write>(output: &Mutex<W>, line: &str) -> io::Result<()> {
+    // a search that panicked while writing left the output usable
+    let mut output = output.lock().unwrap_or_else(PoisonError::into_inner);
+    writeln!(output, "{}", line)?;
+    output.flush()
+}
+
+fn parse_limits<'a>(
+    mut words: impl Iterator<Item = &'a str>
+) -> std::result::Result<Limits, NotationError> {
+    let mut limits = Limits::default();
+    while let Some(word) = words.next() {
+        let value = words.next().and_then(|value| value.parse::<u64>().ok());
+        let value = value.ok_or_else(|| NotationError(format!("{} needs a number", word)))?;
+        match word {
+            "depth" => limits.depth = Some(value.min(u16::MAX as u64) as u16),
+            "movetime" => limits.movetime = Some(Duration::from_millis(value)),
+            "nodes" => limits.nodes = Some(value),
+            _ => return Err(NotationError(format!("unknown limit {}", word))),
+        }
+    }
+    Ok(limits)
+}
+
+/// Sends `bestmove` with the best move found so far when dropped, so
+/// one is sent even if the search panics
+struct BestMove<'a, W: Write> {
+    output: &'a Mutex<W>,
+    game_move: Option<String>,
+}
+
+impl<W: Write> Drop for BestMove<'_, W> {
+    fn drop(&mut self) {
+        let game_move = self.game_move.as_deref().unwrap_or("none");
+        // there is nobody left to tell if the output is gone
+        let _ = send(self.output, &format!("bestmove {}", game_move));
+    }
+}
+
+/// Deepens one ply at a time until a limit is reached, the whole game
+/// tree has been searched or a forced result is found
+fn search<T, W>(
+    board: T,
+    is_max: bool,
+    threads: usize,
+    limits: Limits,
+    stop: Arc<AtomicBool>,
+    output: &Mutex<W>
+) -> io::Result<()>
+where
+    T: Notation + 'static,
+    W: Write,
+{
+    let mut best = BestMove { output, game_move: None };
+    if !board.evaluate().is_over() {
+        // until the first iteration finishes any valid move will do
+        best.game_move = moves_or_pass(&board, is_max).first().map(|m| board.format_move(m));
+    }
+    let started = Instant::now();
+    let deadline = limits.movetime.map(|movetime| started + movetime);
+    let mut nodes = 0;
+    for depth in 1..=limits.depth.unwrap_or(u16::MAX) {
+        let options = SearchOptions {
+            max_nodes: limits.nodes.map(|max_nodes| max_nodes.saturating_sub(nodes)),
+            deadline,
+            stop: Some(Arc::clone(&stop)),
+            observer: None,
+        };
+        let searched = try_get_best_moves_multi(board.clone(), depth, is_max, threads, &options);
+        let Ok((moves, metadata)) = searched else { break };
+        let stats = metadata.stats();
+        nodes += stats.nodes;
+        let Some(first) = moves.into_iter().next() else { break };
+        let elapsed = started.elapsed();
+        let seconds = elapsed.as_secs_f64();
+        let nps = if seconds > 0.0 { (nodes as f64 / seconds) as u64 } else { 0 };
+        send(output, &format!(
+            "info depth {} score {} nodes {} time {} nps {} pv {}",
+            depth,
+            format_value::<T>(first.score),
+            nodes,
+            elapsed.as_millis(),
+            nps,
+            format_line(&board, &first.pv)
+        ))?;
+        best.game_move = Some(board.format_move(&first.game_move));
+        // searching deeper changes nothing once every line has ended
+        if stats.max_depth <= depth || first.score.is_decisive() {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// Formats each move in the position it is played in
+fn format_line<T: Notation>(board: &T, line: &[T::Move]) -> String {
+    let mut board = board.clone();
+    let mut moves = Vec::with_capacity(line.len());
+    for m in line {
+        moves.push(board.format_move(m));
+        board.make_move(m);
+    }
+    moves.join(" ")
+}
@@ -0,0 +1,144 @@
+use crate::{
+    Board, Metadata, MoveScore, PlyStats, Result, SearchError, SearchOptions, ThreadStats, Value,
+    ValueOf,
+};
+use std::sync::atomic::Ordering;
+
+/// A plain recursive minimax over [Board] alone, used as the reference
+/// the pruned searches are checked against. It shares no code with
+/// [crate::search::Searcher], so a bug in the alpha-beta search shows up
+/// as a disagreement rather than in both. Minimax rather than negamax,
+/// since [crate::Score] has no negation.
+struct Reference<'a> {
+    max_depth: u16,
+    metadata: &'a Metadata,
+    options: &'a SearchOptions,
+    nodes: u64,
+    plies: Vec<PlyStats>,
+}
+
+impl Reference<'_> {
+    /// Counts a visited position `depth` plies below a starting move and
+    /// checks it against the limits
+    fn visit(&mut self, depth: u16) -> std::result::Result<(), SearchError> {
+        let total = self.metadata.moves.fetch_add(1, Ordering::Relaxed) + 1;
+        self.nodes += 1;
+        let depth = depth as usize;
+        if self.plies.len() <= depth {
+            self.plies.resize(depth + 1, PlyStats::default());
+        }
+        self.plies[depth].nodes += 1;
+        self.options.check_limits(total as u64, self.nodes)
+    }
+
+    /// Returns the value of `board`, `depth` plies below a starting move,
+    /// with `is_max` to move, and the best line from it
+    fn value<T: Board>(
+        &mut self,
+        board: &mut T,
+        depth: u16,
+        is_max: bool
+    ) -> std::result::Result<(ValueOf<T>, Vec<T::Move>), SearchError> {
+        self.visit(depth)?;
+        let result = board.evaluate();
+        if result.is_over() {
+            self.plies[depth as usize].terminal += 1;
+            let plies = depth.saturating_add(1);
+            let value = match result.winner() {
+                Some(true) => Value::Win(plies),
+                Some(false) => Value::Loss(plies),
+                None => Value::Score(result.score()),
+            };
+            return Ok((value, vec![]));
+        }
+        if depth == self.max_depth {
+            return Ok((Value::Score(result.score()), vec![]));
+        }
+
+        let mut moves = board.get_valid_moves(is_max);
+        if moves.is_empty() {
+            match board.pass_move(is_max) {
+                Some(pass) if !board.get_valid_moves(!is_max).is_empty() => moves.push(pass),
+                _ => return Ok((Value::Score(result.score()), vec![])),
+            }
+        }
+
+        let mut best: Option<(ValueOf<T>, Vec<T::Move>)> = None;
+        for m in moves {
+            let undo = board.make_move(&m);
+            let child = self.value(board, depth + 1, !is_max);
+            board.unmake_move(&m, undo);
+            let (value, line) = child?;
+            let better = match &best {
+                None => true,
+                Some((score, _)) => if is_max { value > *score } else { value < *score },
+            };
+            if better {
+                let mut pv = vec![m];
+                pv.extend(line);
+                best = Some((value, pv));
+            }
+        }
+        Ok(best.expect("there is at least one move"))
+    }
+}
+
+/// Gives every one of `starting_moves` its minimax value, searching
+/// `max_depth` plies below it, and tells the observer about each
+pub(crate) fn root_values<T: Board>(
+    board: &mut T,
+    starting_moves: &[T::Move],
+    max_depth: u16,
+    is_maximizers_turn: bool,
+    metadata: &Metadata,
+    options: &SearchOptions
+) -> std::result::Result<Vec<MoveScore<T>>, SearchError> {
+    let mut reference = Reference { max_depth, metadata, options, nodes: 0, plies: vec![] };
+    let mut results: Vec<MoveScore<T>> = Vec::with_capacity(starting_moves.len());
+    let mut searched = Ok(());
+    for m in starting_moves {
+        let undo = board.make_move(m);
+        let child = reference.value(board, 0, !is_maximizers_turn);
+        board.unmake_move(m, undo);
+        let (score, line) = match child {
+            Ok(child) => child,
+            Err(e) => {
+                searched = Err(e);
+                break;
+            }
+        };
+        let best = results.iter().all(|other| {
+            if is_maximizers_turn { score > other.score } else { score < other.score }
+        });
+        let mut pv = vec![*m];
+        pv.extend(line);
+        results.push(MoveScore { game_move: *m, score, pv });
+        options.observe(|observer| {
+            observer.root_move_searched(m, &score);
+            if best {
+                observer.best_move_changed(m, &score);
+            }
+        });
+    }
+    metadata.record_thread(ThreadStats::new(reference.plies));
+    searched.map(|()| results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::example::three_by_three::TTT;
+
+    #[test]
+    fn counts_every_position() {
+        let mut board = TTT::new('x', 'o');
+        let moves = board.get_valid_moves(true);
+        let metadata = Metadata::new();
+        let options = SearchOptions::default();
+        let results = root_values(&mut board, &moves, 1, true, &metadata, &options).unwrap();
+        assert_eq!(results.len(), 9);
+        // 9 positions after the first move, 8 after each of them
+        assert_eq!(metadata.stats().nodes, 9 + 9 * 8);
+        assert!(results.iter().all(|result| result.pv.len() == 2));
+    }
+}
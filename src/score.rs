@@ -0,0 +1,127 @@
+use std::cmp::Ordering;
+use std::fmt::Debug;
+
+/// A value that the search can order and compare. Implemented for
+/// all of the primitive signed integers; fixed-point types or other
+/// ordered types can implement it as well.
+pub trait Score: Copy + Ord + Send + Sync + Debug + 'static {
+    /// The score of a position that favors neither player.
+    const ZERO: Self;
+}
+
+macro_rules! impl_score {
+    ($($t:ty),*) => {$(
+        impl Score for $t {
+            const ZERO: Self = 0;
+        }
+    )*};
+}
+
+impl_score!(i8, i16, i32, i64, i128, isize);
+
+/// The value the search assigns to a position, always from the
+/// maximizer's point of view. Forced wins and losses are kept apart
+/// from [Score]s so that heuristic evaluations are never distorted by
+/// the distance to the end of the game.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Value<S> {
+    /// The minimizer wins in this many plies
+    Loss(u16),
+    /// A heuristic evaluation, or the score of a drawn game
+    Score(S),
+    /// The maximizer wins in this many plies
+    Win(u16),
+}
+
+impl<S> Value<S> {
+    /// Lower than any value the search will return
+    pub const MIN: Self = Value::Loss(0);
+
+    /// Higher than any value the search will return
+    pub const MAX: Self = Value::Win(0);
+
+    /// Returns the number of plies until the maximizer wins
+    pub fn win_in(&self) -> Option<u16> {
+        match self {
+            Value::Win(plies) => Some(*plies),
+            _ => None,
+        }
+    }
+
+    /// Returns the number of plies until the minimizer wins
+    pub fn loss_in(&self) -> Option<u16> {
+        match self {
+            Value::Loss(plies) => Some(*plies),
+            _ => None,
+        }
+    }
+
+    /// Returns true if the game ends with a winner
+    pub fn is_decisive(&self) -> bool {
+        !matches!(self, Value::Score(_))
+    }
+
+    fn rank(&self) -> u8 {
+        match self {
+            Value::Loss(_) => 0,
+            Value::Score(_) => 1,
+            Value::Win(_) => 2,
+        }
+    }
+}
+
+impl<S: Ord> PartialOrd for Value<S> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Slower losses are greater than faster losses and faster wins are
+/// greater than slower wins, so both players prefer ending the game
+/// sooner when winning and later when losing.
+impl<S: Ord> Ord for Value<S> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Value::Loss(a), Value::Loss(b)) => a.cmp(b),
+            (Value::Score(a), Value::Score(b)) => a.cmp(b),
+            (Value::Win(a), Value::Win(b)) => b.cmp(a),
+            _ => self.rank().cmp(&other.rank()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::Value;
+
+    #[test]
+    fn ordering() {
+        let mut values = vec![
+            Value::Win(5),
+            Value::Score(3i64),
+            Value::Loss(2),
+            Value::Win(1),
+            Value::Score(-50),
+            Value::Loss(6),
+        ];
+        values.sort();
+        assert_eq!(values, vec![
+            Value::Loss(2),
+            Value::Loss(6),
+            Value::Score(-50),
+            Value::Score(3),
+            Value::Win(5),
+            Value::Win(1),
+        ]);
+    }
+
+    #[test]
+    fn bounds() {
+        assert!(Value::<i64>::MIN < Value::Loss(1));
+        assert!(Value::<i64>::MAX > Value::Win(1));
+        assert!(Value::<i64>::MIN < Value::Score(i64::MIN));
+        assert!(Value::<i64>::MAX > Value::Score(i64::MAX));
+    }
+}
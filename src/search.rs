@@ -0,0 +1,404 @@
+use crate::{
+    Board, Metadata, MoveScore, PlyStats, Result, SearchError, SearchObserver, ThreadStats, Value,
+    ValueOf,
+};
+use crate::error::panic_message;
+use std::fmt::{self, Debug};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Arc, Mutex, atomic::{AtomicBool, Ordering}};
+use std::time::Instant;
+use std::vec;
+
+/// How often, in positions visited, each thread checks the clock
+const DEADLINE_INTERVAL: u64 = 1024;
+
+/// Limits that stop a search early, and an observer for its events.
+/// The default has no limits and no observer.
+#[derive(Clone, Default)]
+pub struct SearchOptions {
+    /// Stop with [SearchError::BudgetExhausted] once more than this
+    /// many positions have been visited across all threads
+    pub max_nodes: Option<u64>,
+    /// Stop with [SearchError::BudgetExhausted] once this has passed
+    pub deadline: Option<Instant>,
+    /// Stop with [SearchError::Cancelled] once this is set to true,
+    /// which may be done from any thread
+    pub stop: Option<Arc<AtomicBool>>,
+    /// Told about the progress of the search
+    pub observer: Option<Arc<dyn SearchObserver>>,
+}
+
+impl SearchOptions {
+    /// Sends an event to the observer, if there is one
+    pub(crate) fn observe(&self, event: impl FnOnce(&dyn SearchObserver)) {
+        if let Some(observer) = &self.observer {
+            event(observer.as_ref());
+        }
+    }
+
+    /// Checks the limits after a thread has visited `nodes` positions,
+    /// `total` across all threads
+    pub(crate) fn check_limits(&self, total: u64, nodes: u64) -> std::result::Result<(), SearchError> {
+        if let Some(stop) = &self.stop {
+            if stop.load(Ordering::Relaxed) {
+                return Err(SearchError::Cancelled);
+            }
+        }
+        if let Some(max_nodes) = self.max_nodes {
+            if total > max_nodes {
+                return Err(SearchError::BudgetExhausted);
+            }
+        }
+        if let Some(deadline) = self.deadline {
+            if nodes.is_multiple_of(DEADLINE_INTERVAL) && Instant::now() >= deadline {
+                return Err(SearchError::BudgetExhausted);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Debug for SearchOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SearchOptions")
+            .field("max_nodes", &self.max_nodes)
+            .field("deadline", &self.deadline)
+            .field("stop", &self.stop)
+            .field("observer", &self.observer.is_some())
+            .finish()
+    }
+}
+
+/// Starting moves shared between the threads of a multi threaded search,
+/// and a flag telling them all to stop once one of them has failed
+pub(crate) struct MoveQueue<T: Board> {
+    moves: Mutex<vec::IntoIter<T::Move>>,
+    halted: Arc<AtomicBool>,
+}
+
+impl<T: Board> MoveQueue<T> {
+    pub(crate) fn new(moves: Vec<T::Move>) -> MoveQueue<T> {
+        MoveQueue { moves: Mutex::new(moves.into_iter()), halted: Arc::new(AtomicBool::new(false)) }
+    }
+
+    fn next(&self) -> Option<T::Move> {
+        self.moves.lock().unwrap().next()
+    }
+
+    /// Empties the queue and stops the searches of the other threads,
+    /// which then return [SearchError::Cancelled]
+    fn halt(&self) {
+        self.halted.store(true, Ordering::Relaxed);
+        *self.moves.lock().unwrap() = Vec::new().into_iter();
+    }
+}
+
+/// State for searching from a single thread. The move stack holds one
+/// buffer per ply which is reused by every node at that ply, so move
+/// generation does not allocate once the buffers have grown.
+pub(crate) struct Searcher<T: Board> {
+    max_depth: u16,
+    metadata: Arc<Metadata>,
+    options: SearchOptions,
+    nodes: u64,
+    plies: Vec<PlyStats>,
+    move_stack: Vec<Vec<T::Move>>,
+    /// The best line found from each ply, built up as the search returns
+    pv: Vec<Vec<T::Move>>,
+    /// Set by [MoveQueue::halt] when another thread has failed
+    halted: Option<Arc<AtomicBool>>,
+}
+
+impl<T: Board> Searcher<T> {
+    pub(crate) fn new(
+        max_depth: u16,
+        metadata: Arc<Metadata>,
+        options: SearchOptions
+    ) -> Searcher<T> {
+        Searcher {
+            max_depth,
+            metadata,
+            options,
+            nodes: 0,
+            plies: Vec::new(),
+            move_stack: Vec::new(),
+            pv: Vec::new(),
+            halted: None,
+        }
+    }
+
+    /// The counters for positions `depth` plies below a starting move
+    fn ply(&mut self, depth: u16) -> &mut PlyStats {
+        let depth = depth as usize;
+        if self.plies.len() <= depth {
+            self.plies.resize(depth + 1, PlyStats::default());
+        }
+        &mut self.plies[depth]
+    }
+
+    /// Counts a cutoff after searching `searched` moves of a position
+    fn cutoff(&mut self, depth: u16, searched: usize) {
+        self.metadata.prunes.fetch_add(1, Ordering::Relaxed);
+        let ply = self.ply(depth);
+        ply.cutoffs += 1;
+        if searched == 1 {
+            ply.first_move_cutoffs += 1;
+        }
+        self.options.observe(|observer| observer.cutoff(depth, searched));
+    }
+
+    /// Counts a visited position `depth` plies below a starting move and
+    /// checks it against the limits
+    fn visit(&mut self, depth: u16) -> std::result::Result<(), SearchError> {
+        let total = self.metadata.moves.fetch_add(1, Ordering::Relaxed) + 1;
+        self.nodes += 1;
+        self.ply(depth).nodes += 1;
+        if self.halted.as_ref().is_some_and(|halted| halted.load(Ordering::Relaxed)) {
+            return Err(SearchError::Cancelled);
+        }
+        self.options.check_limits(total as u64, self.nodes)
+    }
+
+    /// Takes the move buffer for `depth` out of the stack so that the
+    /// deeper plies can borrow the searcher while it is iterated. A
+    /// buffer that is not returned because the search stopped early is
+    /// simply replaced by an empty one.
+    fn take_moves(&mut self, depth: u16) -> Vec<T::Move> {
+        let depth = depth as usize;
+        if self.move_stack.len() <= depth {
+            self.move_stack.resize_with(depth + 1, Vec::new);
+        }
+        std::mem::take(&mut self.move_stack[depth])
+    }
+
+    fn return_moves(&mut self, depth: u16, mut moves: Vec<T::Move>) {
+        moves.clear();
+        self.move_stack[depth as usize] = moves;
+    }
+
+    /// Searches starting moves taken from `queue` until it is empty,
+    /// pushing the score of each one onto `results`. If the search
+    /// stops early or panics the queue is halted, so that the other
+    /// threads sharing it stop as well instead of searching the
+    /// remaining moves.
+    pub(crate) fn search_queue(
+        &mut self,
+        board: &mut T,
+        queue: &MoveQueue<T>,
+        results: &Mutex<Vec<MoveScore<T>>>,
+        is_maximizers_turn: bool
+    ) -> std::result::Result<(), SearchError> {
+        self.halted = Some(Arc::clone(&queue.halted));
+        loop {
+            let Some(m) = queue.next() else { break Ok(()) };
+            let searched = panic::catch_unwind(AssertUnwindSafe(|| {
+                self.search_move(board, &m, is_maximizers_turn)
+            }));
+            let searched = searched
+                .unwrap_or_else(|payload| Err(SearchError::WorkerPanicked(panic_message(payload))));
+            match searched {
+                Ok(score) => {
+                    let best = self.record(&mut results.lock().unwrap(), m, score, is_maximizers_turn);
+                    self.report(&m, &score, best);
+                }
+                Err(e) => {
+                    queue.halt();
+                    break Err(e);
+                }
+            }
+        }
+    }
+
+    /// Adds the score of starting move `m` to `results`, returning
+    /// whether it beats every move added before it. Must be called right
+    /// after [Searcher::search_move] so the principal variation is the
+    /// one found for `m`.
+    pub(crate) fn record(
+        &self,
+        results: &mut Vec<MoveScore<T>>,
+        m: T::Move,
+        score: ValueOf<T>,
+        is_maximizers_turn: bool
+    ) -> bool {
+        let better = |other: &MoveScore<T>| {
+            if is_maximizers_turn { score > other.score } else { score < other.score }
+        };
+        let best = results.iter().all(better);
+        let mut pv = vec![m];
+        pv.extend_from_slice(&self.pv[0]);
+        results.push(MoveScore { game_move: m, score, pv });
+        best
+    }
+
+    /// Tells the observer about a starting move added by
+    /// [Searcher::record]. Called once `results` is unlocked again, so
+    /// other threads never wait on the observer and a panicking observer
+    /// cannot poison the results.
+    pub(crate) fn report(&self, m: &T::Move, score: &ValueOf<T>, best: bool) {
+        self.options.observe(|observer| {
+            observer.root_move_searched(m, score);
+            if best {
+                observer.best_move_changed(m, score);
+            }
+        });
+    }
+
+    /// Gets the value of making `m` from the starting position
+    pub(crate) fn search_move(
+        &mut self,
+        board: &mut T,
+        m: &T::Move,
+        is_maximizers_turn: bool
+    ) -> std::result::Result<ValueOf<T>, SearchError> {
+        self.search_child(board, m, 0, ValueOf::<T>::MIN, ValueOf::<T>::MAX, !is_maximizers_turn)
+    }
+
+    /// Makes `m`, searches the position it leads to, `depth` plies below
+    /// a starting move, and takes `m` back again
+    fn search_child(
+        &mut self,
+        board: &mut T,
+        m: &T::Move,
+        depth: u16,
+        alpha: ValueOf<T>,
+        beta: ValueOf<T>,
+        is_max: bool
+    ) -> std::result::Result<ValueOf<T>, SearchError> {
+        self.options.observe(|observer| observer.move_entered(depth, m, &alpha, &beta));
+        let undo = board.make_move(m);
+        let score = self.alphabeta(board, depth, alpha, beta, is_max);
+        board.unmake_move(m, undo);
+        self.options.observe(|observer| {
+            observer.move_exited(depth, m, score.as_ref().ok().map(|score| score as &dyn Debug))
+        });
+        score
+    }
+
+    fn clear_pv(&mut self, depth: u16) {
+        let depth = depth as usize;
+        if self.pv.len() <= depth {
+            self.pv.resize_with(depth + 1, Vec::new);
+        }
+        self.pv[depth].clear();
+    }
+
+    /// Takes `child`, the value of making `m` at `depth`, as the `score`
+    /// if it is better for the player to move, and makes `m` followed by
+    /// the best line after it the best line from `depth`
+    fn improve(
+        &mut self,
+        depth: u16,
+        m: &T::Move,
+        score: &mut ValueOf<T>,
+        child: ValueOf<T>,
+        is_max: bool
+    ) {
+        if if is_max { child <= *score } else { child >= *score } {
+            return;
+        }
+        *score = child;
+        let depth = depth as usize;
+        let (line, rest) = self.pv.split_at_mut(depth + 1);
+        let line = &mut line[depth];
+        line.clear();
+        line.push(*m);
+        line.extend_from_slice(&rest[0]);
+    }
+
+    /// Returns true if `is_max` has a valid move, using the buffer for
+    /// `depth` to generate them
+    fn can_move(&mut self, board: &T, depth: u16, is_max: bool) -> bool {
+        let mut moves = self.take_moves(depth);
+        board.fill_valid_moves(is_max, &mut moves);
+        let can_move = !moves.is_empty();
+        self.return_moves(depth, moves);
+        can_move
+    }
+
+    /// Returns the value of `board` if the search ends there. Otherwise
+    /// fills `moves` with the moves to search, which may be a single pass.
+    fn leaf_value(
+        &mut self,
+        board: &T,
+        depth: u16,
+        is_max: bool,
+        moves: &mut Vec<T::Move>
+    ) -> Option<ValueOf<T>> {
+        let result = board.evaluate();
+        if result.is_over() {
+            self.ply(depth).terminal += 1;
+            // the game ended `depth + 1` plies after the root position
+            let plies = depth.saturating_add(1);
+            return Some(match result.winner() {
+                Some(true) => Value::Win(plies),
+                Some(false) => Value::Loss(plies),
+                None => Value::Score(result.score()),
+            });
+        }
+        if depth == self.max_depth {
+            return Some(Value::Score(result.score()));
+        }
+
+        board.fill_valid_moves(is_max, moves);
+        if moves.is_empty() {
+            match board.pass_move(is_max) {
+                Some(pass) if self.can_move(board, depth + 1, !is_max) => moves.push(pass),
+                // neither player can move, or the game has no passes
+                _ => return Some(Value::Score(result.score())),
+            }
+        }
+        None
+    }
+
+    pub(crate) fn alphabeta(
+        &mut self,
+        board: &mut T,
+        depth: u16,
+        mut alpha: ValueOf<T>,
+        mut beta: ValueOf<T>,
+        is_max: bool
+    ) -> std::result::Result<ValueOf<T>, SearchError> {
+        self.visit(depth)?;
+        self.clear_pv(depth);
+        let mut moves = self.take_moves(depth);
+        if let Some(value) = self.leaf_value(board, depth, is_max, &mut moves) {
+            self.return_moves(depth, moves);
+            return Ok(value);
+        }
+
+        let score = if is_max {
+            let mut score = ValueOf::<T>::MIN;
+            for (i, m) in moves.iter().enumerate() {
+                let child = self.search_child(board, m, depth + 1, alpha, beta, false)?;
+                self.improve(depth, m, &mut score, child, true);
+                alpha = alpha.max(score);
+                if score >= beta {
+                    self.cutoff(depth, i + 1);
+                    break;
+                }
+            }
+            score
+        } else {
+            let mut score = ValueOf::<T>::MAX;
+            for (i, m) in moves.iter().enumerate() {
+                let child = self.search_child(board, m, depth + 1, alpha, beta, true)?;
+                self.improve(depth, m, &mut score, child, false);
+                beta = beta.min(score);
+                if score <= alpha {
+                    self.cutoff(depth, i + 1);
+                    break;
+                }
+            }
+            score
+        };
+
+        self.return_moves(depth, moves);
+        Ok(score)
+    }
+}
+
+impl<T: Board> Drop for Searcher<T> {
+    fn drop(&mut self) {
+        self.metadata.record_thread(ThreadStats::new(std::mem::take(&mut self.plies)));
+    }
+}
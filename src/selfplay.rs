@@ -0,0 +1,168 @@
+use crate::{
+    format_value, moves_or_pass, parse_value, try_get_best_moves_scoped, Board, Notation,
+    Result, Rng, ScoreOf, SearchError, SearchOptions, ValueOf,
+};
+use std::io::{self, BufRead, Write};
+use std::str::FromStr;
+
+/// A position from a self-play game, labelled with the search result
+/// and the result of the game
+#[derive(Clone, Debug)]
+pub struct Sample<T: Board> {
+    pub board: T,
+    pub is_maximizers_turn: bool,
+    /// The best move found by the search, which is not always the move
+    /// that was played, see [SelfPlay::exploration]
+    pub game_move: T::Move,
+    pub score: ValueOf<T>,
+    /// Whether the maximizer won the game, or `None` for a draw
+    pub outcome: Option<bool>,
+}
+
+/// Plays games of the engine against itself and records every position
+/// it searched, as data for tuning [Board::evaluate]
+#[derive(Clone, Debug)]
+pub struct SelfPlay {
+    pub games: usize,
+    /// How deep to search each position, 0 for no limit
+    pub max_depth: u16,
+    /// Threads for each search, 0 for one per cpu
+    pub threads: usize,
+    /// Play a random one of the equally best moves instead of the first,
+    /// so games from the same position differ
+    pub random_best: bool,
+    /// The chance, from 0 to 1, of playing a random valid move instead
+    /// of a best move, to reach positions the engine would avoid
+    pub exploration: f64,
+    /// Games still going after this many plies are counted as draws
+    pub max_plies: usize,
+    /// Seed for the random moves, so the same games can be played again
+    pub seed: u64,
+    pub options: SearchOptions,
+}
+
+impl Default for SelfPlay {
+    fn default() -> Self {
+        SelfPlay {
+            games: 100,
+            max_depth: 0,
+            threads: 0,
+            random_best: true,
+            exploration: 0.0,
+            max_plies: 1000,
+            seed: 0x2545_f491_4f6c_dd1d,
+            options: SearchOptions::default(),
+        }
+    }
+}
+
+impl SelfPlay {
+    /// Plays [SelfPlay::games] games from `board`, returning the samples
+    /// of every game in the order they were played
+    pub fn play<T: Board>(
+        &self,
+        board: &T,
+        is_maximizers_turn: bool
+    ) -> std::result::Result<Vec<Sample<T>>, SearchError> {
+        let mut rng = Rng::new(self.seed);
+        let mut samples = vec![];
+        for _ in 0..self.games {
+            samples.extend(self.play_game(board.clone(), is_maximizers_turn, &mut rng)?);
+        }
+        Ok(samples)
+    }
+
+    fn play_game<T: Board>(
+        &self,
+        mut board: T,
+        mut is_max: bool,
+        rng: &mut Rng
+    ) -> std::result::Result<Vec<Sample<T>>, SearchError> {
+        let mut samples = vec![];
+        while samples.len() < self.max_plies && !board.evaluate().is_over() {
+            let (best, _) = try_get_best_moves_scoped(
+                board.clone(),
+                self.max_depth,
+                is_max,
+                self.threads,
+                &self.options
+            )?;
+            if best.is_empty() {
+                break;
+            }
+            let chosen = if self.random_best { &best[rng.below(best.len())] } else { &best[0] };
+            let played = if rng.next_f64() < self.exploration {
+                let moves = moves_or_pass(&board, is_max);
+                moves[rng.below(moves.len())]
+            } else {
+                chosen.game_move
+            };
+            samples.push(Sample {
+                board: board.clone(),
+                is_maximizers_turn: is_max,
+                game_move: chosen.game_move,
+                score: chosen.score,
+                outcome: None,
+            });
+            board.make_move(&played);
+            is_max = !is_max;
+        }
+        let result = board.evaluate();
+        let outcome = if result.is_over() { result.winner() } else { None };
+        for sample in &mut samples {
+            sample.outcome = outcome;
+        }
+        Ok(samples)
+    }
+}
+
+/// Writes samples one per line as tab separated fields: the position
+/// and best move in the board's [Notation], the score as written by
+/// [crate::Protocol], and the outcome from the maximizer's point of
+/// view as `1`, `0.5` or `0`
+pub fn write_samples<T: Notation>(mut writer: impl Write, samples: &[Sample<T>]) -> io::Result<()> {
+    for sample in samples {
+        let outcome = match sample.outcome {
+            Some(true) => "1",
+            Some(false) => "0",
+            None => "0.5",
+        };
+        writeln!(
+            writer,
+            "{}\t{}\t{}\t{}",
+            sample.board.format_position(sample.is_maximizers_turn),
+            sample.board.format_move(&sample.game_move),
+            format_value::<T>(sample.score),
+            outcome
+        )?;
+    }
+    writer.flush()
+}
+
+/// Reads samples written by [write_samples]
+pub fn read_samples<T>(reader: impl BufRead) -> io::Result<Vec<Sample<T>>>
+where
+    T: Notation,
+    ScoreOf<T>: FromStr,
+{
+    let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidData, message);
+    reader
+        .lines()
+        .map(|line| {
+            let line = line?;
+            let [position, game_move, score, outcome] = line.split('\t').collect::<Vec<_>>()[..] else {
+                return Err(invalid(format!("expected 4 fields in {:?}", line)));
+            };
+            let (board, is_maximizers_turn) = T::parse_position(position).map_err(|e| invalid(e.0))?;
+            let game_move = board.parse_move(game_move, is_maximizers_turn).map_err(|e| invalid(e.0))?;
+            let score = parse_value::<T>(score).map_err(|e| invalid(e.0))?;
+            let outcome = match outcome {
+                "1" => Some(true),
+                "0" => Some(false),
+                "0.5" => None,
+                _ => return Err(invalid(format!("bad outcome {:?}", outcome))),
+            };
+            Ok(Sample { board, is_maximizers_turn, game_move, score, outcome })
+        })
+        .collect()
+}
@@ -0,0 +1,179 @@
+use std::fmt::{self, Display};
+use std::time::Duration;
+
+/// Counters for the positions at one ply of a search
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlyStats {
+    /// Positions visited
+    pub nodes: u64,
+    /// Positions where a cutoff skipped the remaining moves
+    pub cutoffs: u64,
+    /// Cutoffs caused by the first move searched, which happen more
+    /// often the better the moves are ordered
+    pub first_move_cutoffs: u64,
+    /// Positions where the game was over
+    pub terminal: u64,
+}
+
+impl PlyStats {
+    /// The share of cutoffs caused by the first move searched, or 0 if
+    /// there were no cutoffs
+    pub fn first_move_cutoff_rate(&self) -> f64 {
+        if self.cutoffs > 0 { self.first_move_cutoffs as f64 / self.cutoffs as f64 } else { 0.0 }
+    }
+
+    fn add(&mut self, other: &PlyStats) {
+        self.nodes += other.nodes;
+        self.cutoffs += other.cutoffs;
+        self.first_move_cutoffs += other.first_move_cutoffs;
+        self.terminal += other.terminal;
+    }
+}
+
+/// Adds the counters of `plies` to `totals` ply by ply
+pub(crate) fn add_plies(totals: &mut Vec<PlyStats>, plies: &[PlyStats]) {
+    if totals.len() < plies.len() {
+        totals.resize(plies.len(), PlyStats::default());
+    }
+    for (total, ply) in totals.iter_mut().zip(plies) {
+        total.add(ply);
+    }
+}
+
+/// What a single searcher did. Single threaded searches have one of
+/// these, multi threaded searches have one per job or thread.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ThreadStats {
+    /// Positions visited
+    pub nodes: u64,
+    /// Alpha-beta cutoffs
+    pub cutoffs: u64,
+    /// The most plies below the starting position searched
+    pub max_depth: u16,
+    /// The counters for each ply, starting with the positions after
+    /// the starting moves
+    pub plies: Vec<PlyStats>,
+}
+
+impl ThreadStats {
+    pub(crate) fn new(plies: Vec<PlyStats>) -> ThreadStats {
+        ThreadStats {
+            nodes: plies.iter().map(|ply| ply.nodes).sum(),
+            cutoffs: plies.iter().map(|ply| ply.cutoffs).sum(),
+            max_depth: plies.len() as u16,
+            plies,
+        }
+    }
+}
+
+/// A snapshot of the counters of a search, see [crate::Metadata::stats]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SearchStats {
+    /// Positions visited across all threads
+    pub nodes: u64,
+    /// Alpha-beta cutoffs across all threads
+    pub cutoffs: u64,
+    /// Time from the start of the search until it returned, or until
+    /// the snapshot was taken if it is still running
+    pub elapsed: Duration,
+    /// The most plies below the starting position any thread searched
+    pub max_depth: u16,
+    /// The counters for each ply across all threads, starting with the
+    /// positions after the starting moves
+    pub plies: Vec<PlyStats>,
+    /// The counters of each searcher, in the order they finished
+    pub threads: Vec<ThreadStats>,
+}
+
+impl SearchStats {
+    /// Positions visited per second, or 0 if no time has passed
+    pub fn nodes_per_second(&self) -> u64 {
+        let secs = self.elapsed.as_secs_f64();
+        if secs > 0.0 { (self.nodes as f64 / secs) as u64 } else { 0 }
+    }
+
+    /// The effective branching factor of this search: the number of
+    /// moves every position would need for a tree of
+    /// [SearchStats::max_depth] plies to have [SearchStats::nodes]
+    /// positions at its deepest ply, that is `nodes^(1/max_depth)`.
+    /// Each iteration of a deepening search returns its own stats, so
+    /// this is computed per iteration. 0 if nothing was searched.
+    pub fn effective_branching_factor(&self) -> f64 {
+        if self.max_depth == 0 {
+            return 0.0;
+        }
+        (self.nodes as f64).powf(1.0 / self.max_depth as f64)
+    }
+
+    /// How many times more positions were visited at each ply than at
+    /// the ply before it, starting with the second ply over the first.
+    /// Unlike [SearchStats::effective_branching_factor] these show
+    /// where in the tree the search branches most.
+    pub fn ply_ratios(&self) -> Vec<f64> {
+        self.plies.windows(2).map(|pair| pair[1].nodes as f64 / pair[0].nodes as f64).collect()
+    }
+}
+
+impl Display for SearchStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "depth {} nodes {} cutoffs {} time {}ms nps {} ebf {:.2}",
+            self.max_depth,
+            self.nodes,
+            self.cutoffs,
+            self.elapsed.as_millis(),
+            self.nodes_per_second(),
+            self.effective_branching_factor()
+        )?;
+        if self.threads.len() > 1 {
+            let nodes: Vec<u64> = self.threads.iter().map(|thread| thread.nodes).collect();
+            write!(f, " threads {:?}", nodes)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display() {
+        let stats = SearchStats {
+            nodes: 3000,
+            cutoffs: 20,
+            elapsed: Duration::from_millis(1500),
+            max_depth: 4,
+            plies: vec![],
+            threads: vec![
+                ThreadStats { nodes: 1000, cutoffs: 5, max_depth: 4, plies: vec![] },
+                ThreadStats { nodes: 2000, cutoffs: 15, max_depth: 3, plies: vec![] },
+            ],
+        };
+        assert_eq!(stats.nodes_per_second(), 2000);
+        assert_eq!(
+            stats.to_string(),
+            "depth 4 nodes 3000 cutoffs 20 time 1500ms nps 2000 ebf 7.40 threads [1000, 2000]"
+        );
+    }
+
+    #[test]
+    fn effective_branching_factor() {
+        let stats = SearchStats { nodes: 81, max_depth: 4, ..SearchStats::default() };
+        assert!((stats.effective_branching_factor() - 3.0).abs() < 1e-9);
+        let stats = SearchStats { nodes: 3000, max_depth: 4, ..SearchStats::default() };
+        assert!((stats.effective_branching_factor() - 7.4008).abs() < 1e-4);
+        assert_eq!(SearchStats::default().effective_branching_factor(), 0.0);
+    }
+
+    #[test]
+    fn ply_ratios() {
+        let ply = |nodes| PlyStats { nodes, ..PlyStats::default() };
+        let stats = SearchStats { plies: vec![ply(2), ply(4), ply(18)], ..SearchStats::default() };
+        assert_eq!(stats.ply_ratios(), vec![2.0, 4.5]);
+    }
+}
@@ -0,0 +1,315 @@
+//! Property checks for [Board] implementations, meant to be called from
+//! their tests. Every check plays random games from the given position
+//! and panics with the offending move and position on failure. Enabled
+//! by the `test-support` feature.
+
+use crate::{
+    moves_or_pass, perft_verify, try_get_best_moves, try_get_best_moves_minimax,
+    try_get_best_moves_multi, try_get_best_moves_scoped, Board, Result, SearchError,
+    SearchOptions, SearchResult, ValueOf,
+};
+
+pub use crate::Rng;
+
+/// Controls the random games played by the checks
+#[derive(Clone, Debug)]
+pub struct Playouts {
+    /// How many games to play
+    pub games: usize,
+    /// Games still going after this many plies are cut short
+    pub max_plies: usize,
+    /// Seed for the random moves, so failures can be reproduced
+    pub seed: u64,
+}
+
+impl Default for Playouts {
+    fn default() -> Self {
+        Playouts { games: 100, max_plies: 1000, seed: 0x2545_f491_4f6c_dd1d }
+    }
+}
+
+/// Plays random moves on `board` until the game is over, no move can be
+/// made or `max_plies` moves have been made, calling `visit` with every
+/// position reached including the first. Returns the moves made along
+/// with their undo tokens so the caller can take them back.
+fn playout<T: Board>(
+    board: &mut T,
+    mut is_max: bool,
+    rng: &mut Rng,
+    max_plies: usize,
+    mut visit: impl FnMut(&mut T, bool)
+) -> Vec<(T::Move, T::Undo)> {
+    let mut made = vec![];
+    loop {
+        visit(board, is_max);
+        if made.len() == max_plies || board.evaluate().is_over() {
+            return made;
+        }
+        let moves = moves_or_pass(board, is_max);
+        if moves.is_empty() {
+            return made;
+        }
+        let m = moves[rng.below(moves.len())];
+        let undo = board.make_move(&m);
+        made.push((m, undo));
+        is_max = !is_max;
+    }
+}
+
+/// Checks that unmaking every valid move restores the board exactly,
+/// both one move at a time and when unwinding whole games.
+pub fn check_make_unmake<T>(board: &T, is_maximizers_turn: bool, playouts: &Playouts)
+where
+    T: Board + PartialEq,
+{
+    let mut rng = Rng::new(playouts.seed);
+    for _ in 0..playouts.games {
+        let mut game = board.clone();
+        let made = playout(&mut game, is_maximizers_turn, &mut rng, playouts.max_plies, |position, is_max| {
+            let before = position.clone();
+            for m in moves_or_pass(position, is_max) {
+                let undo = position.make_move(&m);
+                position.unmake_move(&m, undo);
+                assert!(
+                    *position == before,
+                    "unmaking {:?} in position {} gave {}", m, before, position
+                );
+            }
+        });
+        for (m, undo) in made.into_iter().rev() {
+            game.unmake_move(&m, undo);
+        }
+        assert!(*board == game, "unwinding a game from {} gave {}", board, game);
+    }
+}
+
+/// Checks that [Board::evaluate] gives the same answer when called
+/// twice, that a position that is not over always has a move or a pass
+/// for the player to move, and that games end within
+/// [Playouts::max_plies].
+pub fn check_terminal_consistency<T: Board>(board: &T, is_maximizers_turn: bool, playouts: &Playouts) {
+    let mut rng = Rng::new(playouts.seed);
+    for _ in 0..playouts.games {
+        let mut game = board.clone();
+        let made = playout(&mut game, is_maximizers_turn, &mut rng, playouts.max_plies, |position, is_max| {
+            let (first, second) = (position.evaluate(), position.evaluate());
+            assert!(
+                first.is_over() == second.is_over() && first.score() == second.score(),
+                "evaluate is not deterministic in position {}", position
+            );
+            assert!(
+                first.is_over() || !moves_or_pass(position, is_max).is_empty(),
+                "position {} is not over but has no moves", position
+            );
+        });
+        assert!(
+            game.evaluate().is_over(),
+            "game from {} not over after {} plies", board, made.len()
+        );
+    }
+}
+
+/// Checks that neither player has a valid move once the game is over.
+/// The search never asks for moves after the end of a game, so this is
+/// not part of [check_all] and boards like the example games that keep
+/// returning the empty squares are fine.
+pub fn check_no_moves_after_game_over<T: Board>(board: &T, is_maximizers_turn: bool, playouts: &Playouts) {
+    let mut rng = Rng::new(playouts.seed);
+    for _ in 0..playouts.games {
+        let mut game = board.clone();
+        playout(&mut game, is_maximizers_turn, &mut rng, playouts.max_plies, |position, _| {
+            if position.evaluate().is_over() {
+                for is_max in [true, false] {
+                    let moves = position.get_valid_moves(is_max);
+                    assert!(
+                        moves.is_empty(),
+                        "position {} is over but has moves {:?}", position, moves
+                    );
+                }
+            }
+        });
+    }
+}
+
+/// Runs every check that applies to any board, which leaves out the
+/// opt in [check_no_moves_after_game_over]
+pub fn check_all<T>(board: &T, is_maximizers_turn: bool, playouts: &Playouts)
+where
+    T: Board + PartialEq,
+{
+    check_make_unmake(board, is_maximizers_turn, playouts);
+    check_terminal_consistency(board, is_maximizers_turn, playouts);
+}
+
+/// Checks [crate::perft] at each depth against `expected`, which starts
+/// at depth 1
+pub fn check_node_counts<T: Board>(board: &T, is_maximizers_turn: bool, expected: &[u64]) {
+    if let Err(mismatch) = perft_verify(board.clone(), is_maximizers_turn, expected) {
+        panic!("{} from {}", mismatch, board);
+    }
+}
+
+/// A search compared by [check_searches_agree]
+pub struct Algorithm<T: Board> {
+    pub name: &'static str,
+    pub search: Box<dyn Fn(T, u16, bool) -> SearchResult<T>>,
+}
+
+/// The searches in this crate: [crate::try_get_best_moves],
+/// [crate::try_get_best_moves_multi], [crate::try_get_best_moves_scoped]
+/// and the unpruned [crate::try_get_best_moves_minimax]
+pub fn default_algorithms<T: Board + 'static>() -> Vec<Algorithm<T>> {
+    vec![
+        Algorithm {
+            name: "single threaded",
+            search: Box::new(|board, depth, is_max| {
+                try_get_best_moves(board, depth, is_max, &SearchOptions::default())
+            }),
+        },
+        Algorithm {
+            name: "multi threaded",
+            search: Box::new(|board, depth, is_max| {
+                try_get_best_moves_multi(board, depth, is_max, 0, &SearchOptions::default())
+            }),
+        },
+        Algorithm {
+            name: "scoped",
+            search: Box::new(|board, depth, is_max| {
+                try_get_best_moves_scoped(board, depth, is_max, 0, &SearchOptions::default())
+            }),
+        },
+        Algorithm {
+            name: "minimax",
+            search: Box::new(|board, depth, is_max| {
+                try_get_best_moves_minimax(board, depth, is_max, &SearchOptions::default())
+            }),
+        },
+    ]
+}
+
+type Outcome<T> = std::result::Result<Vec<(<T as Board>::Move, ValueOf<T>)>, SearchError>;
+
+/// Runs every algorithm on `board`, returning the outcomes if any two
+/// disagree on the set of best moves or their scores
+fn disagreement<T>(
+    board: &T,
+    depth: u16,
+    is_maximizers_turn: bool,
+    algorithms: &[Algorithm<T>]
+) -> Option<Vec<Outcome<T>>>
+where
+    T: Board,
+    T::Move: PartialEq,
+{
+    let outcomes: Vec<Outcome<T>> = algorithms
+        .iter()
+        .map(|algorithm| {
+            (algorithm.search)(board.clone(), depth, is_maximizers_turn).map(|(moves, _)| {
+                moves.into_iter().map(|m| (m.game_move, m.score)).collect()
+            })
+        })
+        .collect();
+    let same = |a: &Outcome<T>, b: &Outcome<T>| match (a, b) {
+        (Ok(a), Ok(b)) => a.len() == b.len() && a.iter().all(|m| b.contains(m)),
+        (a, b) => a.as_ref().err() == b.as_ref().err(),
+    };
+    if outcomes.iter().all(|outcome| same(outcome, &outcomes[0])) {
+        None
+    } else {
+        Some(outcomes)
+    }
+}
+
+/// Plays `line` from `board`, returning `None` if one of its moves is
+/// not valid or is made after the game is over
+fn replay<T: Board>(board: &T, mut is_max: bool, line: &[T::Move]) -> Option<(T, bool)>
+where
+    T::Move: PartialEq,
+{
+    let mut board = board.clone();
+    for m in line {
+        if board.evaluate().is_over() || !moves_or_pass(&board, is_max).contains(m) {
+            return None;
+        }
+        board.make_move(m);
+        is_max = !is_max;
+    }
+    Some((board, is_max))
+}
+
+/// Checks that every algorithm finds the same best moves with the same
+/// scores, searching `depth` plies from positions reached by random
+/// games. The first position with a disagreement is shrunk by taking
+/// pairs of moves out of the line leading to it for as long as the
+/// algorithms still disagree, then reported along with that line and
+/// the outcome of each algorithm.
+pub fn check_searches_agree<T>(
+    board: &T,
+    is_maximizers_turn: bool,
+    depth: u16,
+    playouts: &Playouts,
+    algorithms: &[Algorithm<T>]
+) where
+    T: Board,
+    T::Move: PartialEq,
+{
+    let mut rng = Rng::new(playouts.seed);
+    for _ in 0..playouts.games {
+        let mut game = board.clone();
+        let mut is_max = is_maximizers_turn;
+        let mut line = vec![];
+        while line.len() < playouts.max_plies && !game.evaluate().is_over() {
+            if let Some(outcomes) = disagreement(&game, depth, is_max, algorithms) {
+                report(board, is_maximizers_turn, depth, line, outcomes, algorithms);
+            }
+            let moves = moves_or_pass(&game, is_max);
+            if moves.is_empty() {
+                break;
+            }
+            let m = moves[rng.below(moves.len())];
+            game.make_move(&m);
+            line.push(m);
+            is_max = !is_max;
+        }
+    }
+}
+
+fn report<T>(
+    board: &T,
+    is_maximizers_turn: bool,
+    depth: u16,
+    line: Vec<T::Move>,
+    outcomes: Vec<Outcome<T>>,
+    algorithms: &[Algorithm<T>]
+) -> !
+where
+    T: Board,
+    T::Move: PartialEq,
+{
+    let check = |line: &[T::Move]| {
+        let (position, is_max) = replay(board, is_maximizers_turn, line)?;
+        disagreement(&position, depth, is_max, algorithms).map(|outcomes| (position, is_max, outcomes))
+    };
+    let (position, is_max) = replay(board, is_maximizers_turn, &line).unwrap();
+    let mut failure = (line, position, is_max, outcomes);
+    let mut i = 0;
+    while i + 1 < failure.0.len() {
+        let mut shorter = failure.0.clone();
+        shorter.drain(i..i + 2);
+        if let Some((position, is_max, outcomes)) = check(&shorter) {
+            failure = (shorter, position, is_max, outcomes);
+            i = 0;
+        } else {
+            i += 1;
+        }
+    }
+    let (line, position, is_max, outcomes) = failure;
+    let mut message = format!(
+        "searches disagree at depth {} in position {}\nmaximizer to move: {}\nreached by {:?}",
+        depth, position, is_max, line
+    );
+    for (algorithm, outcome) in algorithms.iter().zip(outcomes) {
+        message.push_str(&format!("\n{}: {:?}", algorithm.name, outcome));
+    }
+    panic!("{}", message);
+}
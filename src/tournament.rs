@@ -0,0 +1,289 @@
+use crate::{
+    moves_or_pass, try_get_best_moves, Board, Result, Rng, SearchError, SearchOptions,
+    SearchResult,
+};
+use std::fmt::{self, Display};
+
+/// One side of a match. `search` is called with the position and
+/// whether it is the maximizer's turn, and the first of the moves it
+/// returns is played, so two engines can differ in depth, threads,
+/// options or search function.
+pub struct Engine<T: Board> {
+    pub name: String,
+    pub search: Box<dyn Fn(T, bool) -> SearchResult<T>>,
+}
+
+impl<T: Board> Engine<T> {
+    /// An engine searching `max_depth` plies with [try_get_best_moves]
+    pub fn with_depth(name: &str, max_depth: u16) -> Engine<T> {
+        Engine {
+            name: name.to_string(),
+            search: Box::new(move |board, is_max| {
+                try_get_best_moves(board, max_depth, is_max, &SearchOptions::default())
+            }),
+        }
+    }
+}
+
+/// A sequential probability ratio test stopping a match once it is
+/// clear whether the first engine is `elo0` or `elo1` stronger than the
+/// second, with false positive rate `alpha` and false negative rate
+/// `beta`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Sprt {
+    pub elo0: f64,
+    pub elo1: f64,
+    pub alpha: f64,
+    pub beta: f64,
+}
+
+impl Default for Sprt {
+    fn default() -> Self {
+        Sprt { elo0: 0.0, elo1: 5.0, alpha: 0.05, beta: 0.05 }
+    }
+}
+
+/// The outcome of a [Sprt]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SprtResult {
+    /// The first engine is at most `elo0` stronger
+    H0,
+    /// The first engine is at least `elo1` stronger
+    H1,
+}
+
+/// Controls how a match is played, see [play_match]
+#[derive(Clone, Debug)]
+pub struct MatchOptions {
+    /// Games to play, rounded up to an even number so every opening is
+    /// played from both sides
+    pub games: usize,
+    /// Random moves played from the starting position before the
+    /// engines take over. Openings that end the game are played again.
+    pub opening_plies: usize,
+    /// Games still going after this many plies are counted as draws
+    pub max_plies: usize,
+    /// Seed for the openings, so matches can be repeated
+    pub seed: u64,
+    /// Stops the match early once the test is decided
+    pub sprt: Option<Sprt>,
+}
+
+impl Default for MatchOptions {
+    fn default() -> Self {
+        MatchOptions { games: 100, opening_plies: 2, max_plies: 1000, seed: 0x2545_f491_4f6c_dd1d, sprt: None }
+    }
+}
+
+/// Games won, drawn and lost by the first engine of a match
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MatchResult {
+    pub wins: u64,
+    pub draws: u64,
+    pub losses: u64,
+    /// Set if the match was stopped early by its [Sprt]
+    pub sprt: Option<SprtResult>,
+}
+
+impl MatchResult {
+    pub fn games(&self) -> u64 {
+        self.wins + self.draws + self.losses
+    }
+
+    /// The share of points the first engine scored, counting a draw as
+    /// half a point, or 0.5 before any games are played
+    pub fn score(&self) -> f64 {
+        match self.games() {
+            0 => 0.5,
+            games => (self.wins as f64 + self.draws as f64 / 2.0) / games as f64,
+        }
+    }
+
+    /// The variance of the points of a single game
+    fn variance(&self) -> f64 {
+        let games = self.games() as f64;
+        if games == 0.0 {
+            return 0.0;
+        }
+        let score = self.score();
+        let (w, d, l) = (self.wins as f64 / games, self.draws as f64 / games, self.losses as f64 / games);
+        w * (1.0 - score).powi(2) + d * (0.5 - score).powi(2) + l * score.powi(2)
+    }
+
+    /// How much stronger the first engine is in Elo, with the margin of
+    /// a 95% confidence interval. The margin is the error of the score
+    /// scaled by the slope of the Elo curve at the score, so it stays
+    /// finite unless the first engine won or lost every game, when both
+    /// are infinite.
+    pub fn elo(&self) -> (f64, f64) {
+        let score = self.score();
+        if score <= 0.0 || score >= 1.0 {
+            return (elo(score), f64::INFINITY);
+        }
+        let error = (self.variance() / self.games().max(1) as f64).sqrt();
+        let slope = 400.0 / (std::f64::consts::LN_10 * score * (1.0 - score));
+        (elo(score), 1.96 * error * slope)
+    }
+
+    /// The log likelihood ratio of [Sprt::elo1] over [Sprt::elo0],
+    /// using a normal approximation of the score
+    pub fn llr(&self, sprt: &Sprt) -> f64 {
+        let variance = self.variance();
+        if variance == 0.0 {
+            return 0.0;
+        }
+        let (score0, score1) = (expected_score(sprt.elo0), expected_score(sprt.elo1));
+        let games = self.games() as f64;
+        games * (score1 - score0) * (2.0 * self.score() - score0 - score1) / (2.0 * variance)
+    }
+
+    fn test(&self, sprt: &Sprt) -> Option<SprtResult> {
+        let llr = self.llr(sprt);
+        if llr >= ((1.0 - sprt.beta) / sprt.alpha).ln() {
+            Some(SprtResult::H1)
+        } else if llr <= (sprt.beta / (1.0 - sprt.alpha)).ln() {
+            Some(SprtResult::H0)
+        } else {
+            None
+        }
+    }
+}
+
+impl Display for MatchResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (elo, margin) = self.elo();
+        write!(f, "W {} D {} L {} elo {:.1} +/- {:.1}", self.wins, self.draws, self.losses, elo, margin)?;
+        match self.sprt {
+            Some(SprtResult::H0) => write!(f, " sprt H0"),
+            Some(SprtResult::H1) => write!(f, " sprt H1"),
+            None => Ok(()),
+        }
+    }
+}
+
+fn elo(score: f64) -> f64 {
+    -400.0 * (1.0 / score - 1.0).log10()
+}
+
+fn expected_score(elo: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf(-elo / 400.0))
+}
+
+/// Plays `first` against `second` from `board`, where
+/// `is_maximizers_turn` says who moves first. Each game starts with
+/// [MatchOptions::opening_plies] random moves, and each opening is
+/// played twice with the engines switching sides, so neither engine
+/// profits from a lucky opening or from moving first. Returns the
+/// result from the point of view of `first`, or the first error
+/// returned by a search.
+pub fn play_match<T>(
+    board: &T,
+    is_maximizers_turn: bool,
+    first: &Engine<T>,
+    second: &Engine<T>,
+    options: &MatchOptions
+) -> std::result::Result<MatchResult, SearchError>
+where
+    T: Board,
+{
+    let mut rng = Rng::new(options.seed);
+    let mut result = MatchResult::default();
+    while result.games() < options.games as u64 {
+        let (opening, is_max) = opening(board, is_maximizers_turn, options.opening_plies, &mut rng);
+        for first_is_max in [true, false] {
+            let (max, min) = if first_is_max { (first, second) } else { (second, first) };
+            match play_game(opening.clone(), is_max, max, min, options.max_plies)? {
+                None => result.draws += 1,
+                Some(winner) if winner == first_is_max => result.wins += 1,
+                Some(_) => result.losses += 1,
+            }
+        }
+        if let Some(sprt) = &options.sprt {
+            result.sprt = result.test(sprt);
+            if result.sprt.is_some() {
+                break;
+            }
+        }
+    }
+    Ok(result)
+}
+
+/// Plays `plies` random moves from `board`, starting over whenever
+/// the game ends first
+fn opening<T: Board>(board: &T, is_maximizers_turn: bool, plies: usize, rng: &mut Rng) -> (T, bool) {
+    for _ in 0..100 {
+        let mut game = board.clone();
+        let mut is_max = is_maximizers_turn;
+        let mut made = 0;
+        while made < plies && !game.evaluate().is_over() {
+            let moves = moves_or_pass(&game, is_max);
+            if moves.is_empty() {
+                break;
+            }
+            game.make_move(&moves[rng.below(moves.len())]);
+            is_max = !is_max;
+            made += 1;
+        }
+        if made == plies && !game.evaluate().is_over() {
+            return (game, is_max);
+        }
+    }
+    // games this short are better played from the start than not at all
+    (board.clone(), is_maximizers_turn)
+}
+
+/// Plays a game to the end, returning whether the maximizer won or
+/// `None` for a draw
+fn play_game<T: Board>(
+    mut board: T,
+    mut is_max: bool,
+    max: &Engine<T>,
+    min: &Engine<T>,
+    max_plies: usize
+) -> std::result::Result<Option<bool>, SearchError> {
+    for _ in 0..max_plies {
+        let result = board.evaluate();
+        if result.is_over() {
+            return Ok(result.winner());
+        }
+        let engine = if is_max { max } else { min };
+        let (moves, _) = (engine.search)(board.clone(), is_max)?;
+        let Some(best) = moves.first() else { break };
+        board.make_move(&best.game_move);
+        is_max = !is_max;
+    }
+    let result = board.evaluate();
+    Ok(if result.is_over() { result.winner() } else { None })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn elo() {
+        let even = MatchResult { wins: 10, draws: 10, losses: 10, sprt: None };
+        assert_eq!(even.elo().0, 0.0);
+        assert!(even.elo().1 > 0.0);
+        let ahead = MatchResult { wins: 30, draws: 0, losses: 10, sprt: None };
+        assert!((ahead.elo().0 - 190.8).abs() < 0.1);
+        let all = MatchResult { wins: 4, draws: 0, losses: 0, sprt: None };
+        assert_eq!(all.elo().0, f64::INFINITY);
+        // lopsided but not perfect, so the margin is finite
+        let lopsided = MatchResult { wins: 9, draws: 1, losses: 0, sprt: None };
+        let (elo, margin) = lopsided.elo();
+        assert!((elo - 511.5).abs() < 0.1);
+        assert!((margin - 340.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn sprt() {
+        let sprt = Sprt::default();
+        let ahead = MatchResult { wins: 300, draws: 100, losses: 100, sprt: None };
+        assert_eq!(ahead.test(&sprt), Some(SprtResult::H1));
+        let behind = MatchResult { wins: 100, draws: 100, losses: 300, sprt: None };
+        assert_eq!(behind.test(&sprt), Some(SprtResult::H0));
+        let unclear = MatchResult { wins: 3, draws: 2, losses: 3, sprt: None };
+        assert_eq!(unclear.test(&sprt), None);
+    }
+}
@@ -0,0 +1,205 @@
+use crate::SearchObserver;
+use std::collections::HashMap;
+use std::fmt::{Debug, Write};
+use std::sync::Mutex;
+use std::thread::{self, ThreadId};
+
+/// A position in a [Tree], along with the move that led to it. Moves
+/// and scores are formatted with [Debug].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TreeNode {
+    pub game_move: String,
+    /// Plies below a starting move, or `None` for the root
+    pub depth: Option<u16>,
+    /// The window the position was searched with
+    pub alpha: String,
+    pub beta: String,
+    /// The value found for the position, or `None` if the search stopped
+    /// before finishing it
+    pub score: Option<String>,
+    /// Whether a cutoff skipped some of the moves from this position
+    pub cutoff: bool,
+    /// Indices of the recorded positions after each move searched
+    pub children: Vec<usize>,
+}
+
+/// The positions recorded by a [TreeRecorder]. The first node is the
+/// starting position, the parent of the starting moves.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Tree {
+    pub nodes: Vec<TreeNode>,
+}
+
+/// Records the tree explored by a search. Set it as the
+/// [crate::SearchOptions::observer], then read it with
+/// [TreeRecorder::tree]. Every thread of a multi threaded search
+/// records the starting moves it takes. Trees grow quickly, so only
+/// positions up to `max_depth` plies below a starting move are recorded
+/// and recording stops after `max_nodes` positions.
+pub struct TreeRecorder {
+    max_depth: u16,
+    max_nodes: usize,
+    state: Mutex<Recording>,
+}
+
+struct Recording {
+    nodes: Vec<TreeNode>,
+    /// The nodes each thread has entered but not exited yet, `None`
+    /// for nodes over the limits
+    paths: HashMap<ThreadId, Vec<Option<usize>>>,
+}
+
+impl TreeRecorder {
+    pub fn new(max_depth: u16, max_nodes: usize) -> TreeRecorder {
+        let root = TreeNode {
+            game_move: String::from("root"),
+            depth: None,
+            alpha: String::new(),
+            beta: String::new(),
+            score: None,
+            cutoff: false,
+            children: vec![],
+        };
+        TreeRecorder {
+            max_depth,
+            max_nodes,
+            state: Mutex::new(Recording { nodes: vec![root], paths: HashMap::new() }),
+        }
+    }
+
+    /// Returns a copy of what has been recorded so far
+    pub fn tree(&self) -> Tree {
+        Tree { nodes: self.state.lock().unwrap().nodes.clone() }
+    }
+}
+
+impl SearchObserver for TreeRecorder {
+    fn move_entered(&self, depth: u16, game_move: &dyn Debug, alpha: &dyn Debug, beta: &dyn Debug) {
+        let mut state = self.state.lock().unwrap();
+        let Recording { nodes, paths } = &mut *state;
+        let path = paths.entry(thread::current().id()).or_default();
+        let parent = match path.last() {
+            Some(parent) => *parent,
+            None => Some(0),
+        };
+        let recorded = match parent {
+            Some(parent) if depth <= self.max_depth && nodes.len() <= self.max_nodes => {
+                nodes.push(TreeNode {
+                    game_move: format!("{:?}", game_move),
+                    depth: Some(depth),
+                    alpha: format!("{:?}", alpha),
+                    beta: format!("{:?}", beta),
+                    score: None,
+                    cutoff: false,
+                    children: vec![],
+                });
+                let index = nodes.len() - 1;
+                nodes[parent].children.push(index);
+                Some(index)
+            }
+            _ => None,
+        };
+        path.push(recorded);
+    }
+
+    fn move_exited(&self, _depth: u16, _game_move: &dyn Debug, score: Option<&dyn Debug>) {
+        let mut state = self.state.lock().unwrap();
+        let Recording { nodes, paths } = &mut *state;
+        let exited = paths.get_mut(&thread::current().id()).and_then(|path| path.pop());
+        if let (Some(Some(index)), Some(score)) = (exited, score) {
+            nodes[index].score = Some(format!("{:?}", score));
+        }
+    }
+
+    fn cutoff(&self, _depth: u16, _searched: usize) {
+        let mut state = self.state.lock().unwrap();
+        let Recording { nodes, paths } = &mut *state;
+        let current = paths.get(&thread::current().id()).and_then(|path| path.last());
+        if let Some(Some(index)) = current {
+            nodes[*index].cutoff = true;
+        }
+    }
+}
+
+impl Tree {
+    /// Formats the tree for Graphviz. Positions that were cut off are
+    /// drawn in red.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph tree {\n    node [shape=box];\n");
+        for (i, node) in self.nodes.iter().enumerate() {
+            let mut label = node.game_move.clone();
+            if node.depth.is_some() {
+                write!(label, "\n[{}, {}]", node.alpha, node.beta).unwrap();
+            }
+            if let Some(score) = &node.score {
+                write!(label, "\n{}", score).unwrap();
+            }
+            let color = if node.cutoff { ", color=red" } else { "" };
+            writeln!(dot, "    n{} [label=\"{}\"{}];", i, escape(&label), color).unwrap();
+            for child in &node.children {
+                writeln!(dot, "    n{} -> n{};", i, child).unwrap();
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Formats the tree as nested JSON objects, starting from the root
+    pub fn to_json(&self) -> String {
+        let mut json = String::new();
+        if !self.nodes.is_empty() {
+            self.write_json(0, &mut json);
+        }
+        json
+    }
+
+    fn write_json(&self, index: usize, json: &mut String) {
+        let node = &self.nodes[index];
+        let optional = |value: Option<&str>| {
+            value.map_or(String::from("null"), |value| format!("\"{}\"", escape(value)))
+        };
+        write!(
+            json,
+            "{{\"move\":\"{}\",\"depth\":{},\"alpha\":\"{}\",\"beta\":\"{}\",\"score\":{},\"cutoff\":{},\"children\":[",
+            escape(&node.game_move),
+            node.depth.map_or(String::from("null"), |depth| depth.to_string()),
+            escape(&node.alpha),
+            escape(&node.beta),
+            optional(node.score.as_deref()),
+            node.cutoff
+        )
+        .unwrap();
+        for (i, child) in node.children.iter().enumerate() {
+            if i > 0 {
+                json.push(',');
+            }
+            self.write_json(*child, json);
+        }
+        json.push_str("]}");
+    }
+}
+
+/// Escapes a string for a JSON string or a quoted DOT label
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            c if (c as u32) < 0x20 => write!(escaped, "\\u{:04x}", c as u32).unwrap(),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escaping() {
+        assert_eq!(escape("Move { to: \"a\\b\" }\n"), "Move { to: \\\"a\\\\b\\\" }\\n");
+    }
+}
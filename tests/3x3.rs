@@ -1,8 +1,10 @@
+#![allow(clippy::needless_range_loop)]
+
 #[cfg(test)]
 mod tests {
 
-    use multithread_minimax::example::four_by_four::TTT;
-    use multithread_minimax::get_best_moves_multi;
+    use multithread_minimax::example::{four_by_four::TTT, three_by_three};
+    use multithread_minimax::{get_best_moves, get_best_moves_multi, Board, Result};
 
     #[test]
     fn prevent_win_x_0() {
@@ -61,4 +63,60 @@ mod tests {
         assert_eq!(moves.len(), 1);
         assert_eq!(moves[0].game_move.to_position, 3);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn win_in_one() {
+        let mut game = three_by_three::TTT::new('x', 'o');
+        game.board = [
+            Some('x'),
+            Some('x'),
+            None,
+            Some('o'),
+            Some('o'),
+            None,
+            None,
+            None,
+            None
+        ];
+        let (moves, _) = get_best_moves(game, 0, true);
+        assert_eq!(moves.len(), 1);
+        assert_eq!(moves[0].game_move.to_position, 2);
+        assert_eq!(moves[0].score.win_in(), Some(1));
+        assert_eq!(moves[0].pv, vec![moves[0].game_move]);
+    }
+
+    #[test]
+    fn loss_in_two() {
+        let mut game = three_by_three::TTT::new('x', 'o');
+        game.board = [
+            Some('o'),
+            None,
+            Some('o'),
+            None,
+            Some('x'),
+            None,
+            Some('o'),
+            None,
+            Some('x')
+        ];
+        let (moves, _) = get_best_moves(game, 0, true);
+        assert_eq!(moves.len(), 4);
+        assert!(moves.iter().all(|m| m.score.loss_in() == Some(2)));
+    }
+
+    #[test]
+    fn principal_variation() {
+        let (moves, _) = get_best_moves_multi(three_by_three::TTT::new('x', 'o'), 0, true, 0);
+        for m in &moves {
+            // perfect play fills the board
+            assert_eq!(m.pv.len(), 9);
+            assert_eq!(m.pv[0], m.game_move);
+            let mut game = three_by_three::TTT::new('x', 'o');
+            for pv_move in &m.pv {
+                game.make_move(pv_move);
+            }
+            assert!(game.evaluate().is_over());
+            assert_eq!(game.evaluate().score(), 0);
+        }
+    }
+}
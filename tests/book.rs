@@ -0,0 +1,120 @@
+mod tests {
+
+    use multithread_minimax::example::{four_by_four, three_by_three};
+    use multithread_minimax::{
+        get_best_moves, try_get_best_moves_with_book, Board, Book, BookBuilder, BookMove,
+        OpeningBook, Recommendation, Result, SearchOptions,
+    };
+    use std::fmt::{self, Display};
+
+    /// A game that never ends, where each player either stays put or
+    /// moves a counter up by one, so positions come back after a
+    /// different number of plies
+    #[derive(Clone, Debug, Hash)]
+    struct Line(u8);
+
+    struct LineResult(u8);
+
+    impl Result for LineResult {
+        type Score = i32;
+
+        fn is_over(&self) -> bool {
+            false
+        }
+
+        fn score(&self) -> i32 {
+            self.0.into()
+        }
+    }
+
+    impl Display for Line {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "{}", self.0)
+        }
+    }
+
+    impl Board for Line {
+        type Move = u8;
+        type Result = LineResult;
+        type Undo = ();
+
+        fn make_move(&mut self, step: &u8) {
+            self.0 += step;
+        }
+
+        fn unmake_move(&mut self, step: &u8, _: ()) {
+            self.0 -= step;
+        }
+
+        fn get_valid_moves(&self, _: bool) -> Vec<u8> {
+            vec![0, 1]
+        }
+
+        fn evaluate(&self) -> LineResult {
+            LineResult(self.0)
+        }
+    }
+
+    #[test]
+    fn insert_and_lookup() {
+        let game = three_by_three::TTT::new('x', 'o');
+        let corner = three_by_three::Move { player: 'x', to_position: 0 };
+        let invalid = three_by_three::Move { player: 'o', to_position: 0 };
+        let mut book = Book::new();
+        let moves = [BookMove { game_move: corner, weight: 3 }, BookMove { game_move: invalid, weight: 1 }];
+        book.insert(&game, true, &moves);
+        assert_eq!(book.len(), 1);
+        assert_eq!(book.lookup(&game, true), vec![BookMove { game_move: corner, weight: 3 }]);
+        assert!(book.lookup(&game, false).is_empty());
+    }
+
+    #[test]
+    fn build_and_round_trip() {
+        let game = four_by_four::TTT::new('x', 'o');
+        let builder = BookBuilder { plies: 2, search_depth: 2, ..BookBuilder::default() };
+        let book = builder.build(&game, true).unwrap();
+        // the starting position and the 16 after the first move
+        assert_eq!(book.len(), 17);
+
+        let (best, _) = get_best_moves(game, 2, true);
+        let moves: Vec<_> = book.lookup(&game, true).into_iter().map(|m| m.game_move).collect();
+        assert_eq!(moves, best.iter().map(|m| m.game_move).collect::<Vec<_>>());
+
+        let mut file = vec![];
+        book.write_to(&mut file).unwrap();
+        assert_eq!(&file[..4], b"MMBK");
+        assert_eq!(Book::read_from(&file[..]).unwrap(), book);
+        assert!(Book::read_from(&file[1..]).is_err());
+        assert!(Book::read_from(&file[..file.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn consulted_before_search() {
+        let mut game = three_by_three::TTT::new('x', 'o');
+        let book = BookBuilder { plies: 1, ..BookBuilder::default() }.build(&game, true).unwrap();
+        let options = SearchOptions::default();
+        match try_get_best_moves_with_book(game, 0, true, &book, &options).unwrap() {
+            Recommendation::Book(moves) => assert_eq!(moves.len(), 9),
+            Recommendation::Searched(..) => panic!("expected book moves"),
+        }
+        game.make_move(&three_by_three::Move { player: 'x', to_position: 4 });
+        match try_get_best_moves_with_book(game, 0, false, &book, &options).unwrap() {
+            Recommendation::Searched(moves, _) => assert_eq!(moves.len(), 4),
+            Recommendation::Book(..) => panic!("expected a search"),
+        }
+    }
+
+    #[test]
+    fn positions_reached_again_sooner() {
+        let builder = BookBuilder { plies: 4, search_depth: 1, threads: 1, ..BookBuilder::default() };
+        let book = builder.build(&Line(0), true).unwrap();
+        // 1 with the minimizer to move is first reached after three
+        // plies, staying put twice, and then after one, moving up, which
+        // leaves room for 2 with the maximizer to move after it
+        assert!(!book.lookup(&Line(2), true).is_empty());
+        // every position within three plies: 0 and 1 for either player,
+        // 2 for the maximizer after two plies, 2 and 3 for the minimizer
+        // after three
+        assert_eq!(book.len(), 7);
+    }
+}
@@ -0,0 +1,56 @@
+mod common;
+
+mod tests {
+
+    use crate::common::Wrapper;
+    use multithread_minimax::example::three_by_three::TTT;
+    use multithread_minimax::{
+        get_best_moves_scoped, try_get_best_moves_checked, Checked, SearchError, SearchOptions,
+        ViolationKind,
+    };
+
+    /// `sticky` boards never clear the center when a move is unmade and
+    /// `doubled` boards generate every move twice
+    fn board(sticky: bool, doubled: bool) -> Wrapper<'static, TTT> {
+        let sticky = if sticky { Some(4) } else { None };
+        Wrapper { sticky, doubled, ..Wrapper::new(TTT::new('x', 'o')) }
+    }
+
+    #[test]
+    fn valid_board() {
+        let options = SearchOptions::default();
+        let (moves, _) = try_get_best_moves_checked(TTT::new('x', 'o'), 0, true, &options).unwrap();
+        assert_eq!(moves.len(), 9);
+    }
+
+    #[test]
+    fn not_restored() {
+        let options = SearchOptions::default();
+        match try_get_best_moves_checked(board(true, false), 2, true, &options) {
+            Err(SearchError::InvalidBoard(violation)) => {
+                assert_eq!(violation.kind, ViolationKind::NotRestored);
+                assert!(violation.game_move.contains("to_position: 4"));
+            }
+            _ => panic!("expected a violation"),
+        }
+    }
+
+    #[test]
+    fn duplicate_move() {
+        let options = SearchOptions::default();
+        match try_get_best_moves_checked(board(false, true), 1, true, &options) {
+            Err(SearchError::InvalidBoard(violation)) => {
+                assert_eq!(violation.kind, ViolationKind::DuplicateMove);
+                assert_eq!(violation.position, TTT::new('x', 'o').to_string());
+            }
+            _ => panic!("expected a violation"),
+        }
+    }
+
+    #[test]
+    fn multi_threaded() {
+        let checked = Checked::new(board(true, false));
+        get_best_moves_scoped(checked.clone(), 2, true, 0);
+        assert_eq!(checked.violation().unwrap().kind, ViolationKind::NotRestored);
+    }
+}
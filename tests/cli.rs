@@ -0,0 +1,42 @@
+mod tests {
+
+    use std::io::Write;
+    use std::process::{Command, Output, Stdio};
+
+    fn run(args: &[&str], input: &str) -> Output {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_ttt"))
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap();
+        child.stdin.take().unwrap().write_all(input.as_bytes()).unwrap();
+        child.wait_with_output().unwrap()
+    }
+
+    #[test]
+    fn engine_blocks() {
+        // x threatens the top row and o has to block at 2
+        let output = run(&["--side", "x", "--threads", "1"], "0\n1\nquit\n");
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        assert!(output.status.success());
+        assert!(stdout.contains("engine plays 2"), "{}", stdout);
+    }
+
+    #[test]
+    fn rejects_taken_squares() {
+        let output = run(&["--game", "4", "--side", "o", "--depth", "2", "--analysis"], "0\n");
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        assert!(stdout.contains("engine plays 0"), "{}", stdout);
+        assert!(stdout.contains("nodes"));
+        assert!(stdout.contains("0 is not a free square"));
+    }
+
+    #[test]
+    fn bad_option() {
+        let output = run(&["--side", "z"], "");
+        assert_eq!(output.status.code(), Some(2));
+        assert!(String::from_utf8(output.stderr).unwrap().contains("unknown side z"));
+    }
+}
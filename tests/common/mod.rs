@@ -0,0 +1,123 @@
+//! A wrapper around the example games shared by the tests of boards
+//! that behave differently from them in one way or another
+#![allow(dead_code)]
+
+use multithread_minimax::example::{four_by_four, three_by_three};
+use multithread_minimax::Board;
+use std::fmt::{self, Display};
+
+/// The example games, which number their squares the same way
+pub trait Example: Board {
+    fn square(game_move: &Self::Move) -> usize;
+}
+
+impl Example for three_by_three::TTT {
+    fn square(game_move: &three_by_three::Move) -> usize {
+        game_move.to_position
+    }
+}
+
+impl Example for four_by_four::TTT {
+    fn square(game_move: &four_by_four::Move) -> usize {
+        game_move.to_position
+    }
+}
+
+/// Delegates to `game` except where one of the fields below says
+/// otherwise. Start from [Wrapper::new] and set the fields a test needs.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Wrapper<'a, B: Example> {
+    pub game: B,
+    /// Moves made and not unmade yet. Kept on the heap so the wrapper
+    /// is never `Copy`, and checked against the moves being unmade.
+    pub made: Vec<B::Move>,
+    /// Moves are generated in the order of their squares in this
+    /// borrowed table, so the wrapper is not `'static` either
+    pub order: Option<&'a [usize]>,
+    /// Moves are unmade by restoring a copy of `game` returned from
+    /// `make_move`, as if every move were irreversible
+    pub snapshots: bool,
+    /// No moves are generated although the game is not over
+    pub stuck: bool,
+    /// `evaluate` panics once a move has been made
+    pub panics: bool,
+    /// `evaluate` panics below a first move to this square
+    pub panics_under: Option<usize>,
+    /// A move to this square is never taken back by `unmake_move`
+    pub sticky: Option<usize>,
+    /// Every move is generated twice
+    pub doubled: bool,
+}
+
+impl<B: Example> Wrapper<'_, B> {
+    pub fn new(game: B) -> Self {
+        Wrapper {
+            game,
+            made: vec![],
+            order: None,
+            snapshots: false,
+            stuck: false,
+            panics: false,
+            panics_under: None,
+            sticky: None,
+            doubled: false,
+        }
+    }
+}
+
+impl<B: Example> Display for Wrapper<'_, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.game, f)
+    }
+}
+
+impl<B> Board for Wrapper<'_, B>
+where
+    B: Example,
+    B::Move: PartialEq,
+{
+    type Move = B::Move;
+    type Result = B::Result;
+    type Undo = (B::Undo, Option<B>);
+
+    fn make_move(&mut self, valid_move: &B::Move) -> Self::Undo {
+        let snapshot = if self.snapshots { Some(self.game.clone()) } else { None };
+        self.made.push(*valid_move);
+        (self.game.make_move(valid_move), snapshot)
+    }
+
+    fn unmake_move(&mut self, made_move: &B::Move, (undo, snapshot): Self::Undo) {
+        assert_eq!(self.made.pop().as_ref(), Some(made_move));
+        if self.sticky == Some(B::square(made_move)) {
+            return;
+        }
+        match snapshot {
+            Some(game) => self.game = game,
+            None => self.game.unmake_move(made_move, undo),
+        }
+    }
+
+    fn get_valid_moves(&self, is_maximizer: bool) -> Vec<B::Move> {
+        if self.stuck {
+            return vec![];
+        }
+        let mut moves = self.game.get_valid_moves(is_maximizer);
+        if let Some(order) = self.order {
+            moves.sort_by_key(|m| order.iter().position(|square| *square == B::square(m)));
+        }
+        if self.doubled {
+            moves.extend(moves.clone());
+        }
+        moves
+    }
+
+    fn evaluate(&self) -> B::Result {
+        if self.panics && !self.made.is_empty() {
+            panic!("evaluate panicked");
+        }
+        if self.panics_under.is_some() && self.panics_under == self.made.first().map(B::square) {
+            panic!("evaluate panicked");
+        }
+        self.game.evaluate()
+    }
+}
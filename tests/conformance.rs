@@ -0,0 +1,47 @@
+mod common;
+
+mod tests {
+
+    use crate::common::Wrapper;
+    use multithread_minimax::example::{four_by_four, three_by_three};
+    use multithread_minimax::testing::{
+        check_all, check_make_unmake, check_no_moves_after_game_over, check_node_counts,
+        check_terminal_consistency, Playouts,
+    };
+
+    #[test]
+    fn three_by_three() {
+        let game = three_by_three::TTT::new('x', 'o');
+        check_all(&game, true, &Playouts::default());
+        // no game can end before the fifth move
+        check_node_counts(&game, true, &[9, 72, 504, 3024, 15120, 54720]);
+    }
+
+    #[test]
+    fn four_by_four() {
+        let game = four_by_four::TTT::new('x', 'o');
+        check_all(&game, false, &Playouts::default());
+        check_node_counts(&game, false, &[16, 240, 3360]);
+    }
+
+    #[test]
+    #[should_panic(expected = "unmaking")]
+    fn move_not_unmade() {
+        let game = Wrapper { sticky: Some(4), ..Wrapper::new(three_by_three::TTT::new('x', 'o')) };
+        check_make_unmake(&game, true, &Playouts::default());
+    }
+
+    #[test]
+    #[should_panic(expected = "is not over but has no moves")]
+    fn no_moves_before_game_over() {
+        let game = Wrapper { stuck: true, ..Wrapper::new(three_by_three::TTT::new('x', 'o')) };
+        check_terminal_consistency(&game, true, &Playouts::default());
+    }
+
+    #[test]
+    #[should_panic(expected = "is over but has moves")]
+    fn moves_after_game_over() {
+        // the example games keep returning the empty squares after a win
+        check_no_moves_after_game_over(&three_by_three::TTT::new('x', 'o'), true, &Playouts::default());
+    }
+}
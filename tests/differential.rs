@@ -0,0 +1,39 @@
+mod tests {
+
+    use multithread_minimax::example::{four_by_four, three_by_three};
+    use multithread_minimax::testing::{check_searches_agree, default_algorithms, Algorithm, Playouts};
+    use multithread_minimax::{try_get_best_moves, SearchOptions};
+
+    #[test]
+    fn three_by_three() {
+        let game = three_by_three::TTT::new('x', 'o');
+        let playouts = Playouts { games: 3, ..Playouts::default() };
+        check_searches_agree(&game, true, 0, &playouts, &default_algorithms());
+    }
+
+    #[test]
+    fn four_by_four() {
+        let game = four_by_four::TTT::new('x', 'o');
+        let playouts = Playouts { games: 2, max_plies: 12, ..Playouts::default() };
+        check_searches_agree(&game, true, 3, &playouts, &default_algorithms());
+    }
+
+    #[test]
+    #[should_panic(expected = "reached by []")]
+    fn reports_disagreement() {
+        let game = three_by_three::TTT::new('x', 'o');
+        let mut algorithms = default_algorithms();
+        // only ever returns one of the best moves
+        algorithms.push(Algorithm {
+            name: "first best move",
+            search: Box::new(|board, depth, is_max| {
+                try_get_best_moves(board, depth, is_max, &SearchOptions::default())
+                    .map(|(mut moves, metadata)| {
+                        moves.truncate(1);
+                        (moves, metadata)
+                    })
+            }),
+        });
+        check_searches_agree(&game, true, 2, &Playouts::default(), &algorithms);
+    }
+}
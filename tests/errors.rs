@@ -0,0 +1,105 @@
+mod common;
+
+mod tests {
+
+    use crate::common::Wrapper;
+    use std::fmt::Debug;
+    use std::sync::{Arc, atomic::{AtomicBool, AtomicUsize, Ordering}};
+    use std::time::Instant;
+    use multithread_minimax::example::four_by_four::TTT;
+    use multithread_minimax::{
+        try_get_best_moves, try_get_best_moves_multi, try_get_best_moves_scoped,
+        SearchError, SearchObserver, SearchOptions, SearchResult,
+    };
+
+    /// Misbehaves on purpose: `stuck` boards have no moves although
+    /// the game is not over and `panics` boards panic when evaluated
+    /// after a move has been made
+    fn board(stuck: bool, panics: bool) -> Wrapper<'static, TTT> {
+        Wrapper { stuck, panics, ..Wrapper::new(TTT::new('x', 'o')) }
+    }
+
+    #[test]
+    fn no_legal_moves() {
+        let options = SearchOptions::default();
+        let error = Err(SearchError::NoLegalMoves);
+        assert_eq!(try_get_best_moves(board(true, false), 0, true, &options).map(|_| ()), error);
+        assert_eq!(try_get_best_moves_multi(board(true, false), 0, true, 2, &options).map(|_| ()), error);
+        assert_eq!(try_get_best_moves_scoped(board(true, false), 0, true, 2, &options).map(|_| ()), error);
+    }
+
+    #[test]
+    fn cancelled() {
+        let options = SearchOptions {
+            stop: Some(Arc::new(AtomicBool::new(true))),
+            ..SearchOptions::default()
+        };
+        let error = Err(SearchError::Cancelled);
+        assert_eq!(try_get_best_moves(board(false, false), 0, true, &options).map(|_| ()), error);
+        assert_eq!(try_get_best_moves_multi(board(false, false), 0, true, 2, &options).map(|_| ()), error);
+        assert_eq!(try_get_best_moves_scoped(board(false, false), 0, true, 2, &options).map(|_| ()), error);
+    }
+
+    #[test]
+    fn node_budget() {
+        let options = SearchOptions {
+            max_nodes: Some(1000),
+            ..SearchOptions::default()
+        };
+        let error = Err(SearchError::BudgetExhausted);
+        assert_eq!(try_get_best_moves(board(false, false), 0, true, &options).map(|_| ()), error);
+        assert_eq!(try_get_best_moves_multi(board(false, false), 0, true, 2, &options).map(|_| ()), error);
+        assert_eq!(try_get_best_moves_scoped(board(false, false), 0, true, 2, &options).map(|_| ()), error);
+    }
+
+    #[test]
+    fn deadline() {
+        let options = SearchOptions {
+            deadline: Some(Instant::now()),
+            ..SearchOptions::default()
+        };
+        let error = Err(SearchError::BudgetExhausted);
+        assert_eq!(try_get_best_moves(board(false, false), 0, true, &options).map(|_| ()), error);
+        assert_eq!(try_get_best_moves_scoped(board(false, false), 0, true, 2, &options).map(|_| ()), error);
+    }
+
+    #[test]
+    fn worker_panicked() {
+        let options = SearchOptions::default();
+        assert_eq!(
+            try_get_best_moves_multi(board(false, true), 2, true, 2, &options).map(|_| ()),
+            Err(SearchError::WorkerPanicked(String::from("evaluate panicked")))
+        );
+        assert_eq!(
+            try_get_best_moves_scoped(board(false, true), 2, true, 2, &options).map(|_| ()),
+            Err(SearchError::WorkerPanicked(String::from("evaluate panicked")))
+        );
+    }
+
+    #[derive(Default)]
+    struct Searched(AtomicUsize);
+
+    impl SearchObserver for Searched {
+        fn root_move_searched(&self, _game_move: &dyn Debug, _score: &dyn Debug) {
+            self.0.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    #[test]
+    fn panic_halts_other_threads() {
+        type Board = Wrapper<'static, TTT>;
+        type Search = fn(Board, u16, bool, usize, &SearchOptions) -> SearchResult<Board>;
+        for search in [try_get_best_moves_multi as Search, try_get_best_moves_scoped] {
+            let searched = Arc::new(Searched::default());
+            let options = SearchOptions { observer: Some(searched.clone()), ..SearchOptions::default() };
+            // only the first of the 16 starting moves panics
+            let board = Wrapper { panics_under: Some(0), ..Wrapper::new(TTT::new('x', 'o')) };
+            assert_eq!(
+                search(board, 5, true, 2, &options).map(|_| ()),
+                Err(SearchError::WorkerPanicked(String::from("evaluate panicked")))
+            );
+            // the other thread stops instead of searching the other 15
+            assert!(searched.0.load(Ordering::Relaxed) < 15);
+        }
+    }
+}
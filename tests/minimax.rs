@@ -0,0 +1,45 @@
+mod tests {
+
+    use multithread_minimax::example::{four_by_four, three_by_three};
+    use multithread_minimax::{
+        get_best_moves, get_best_moves_cross_checked, try_get_best_moves_minimax, Board,
+        SearchOptions,
+    };
+
+    #[test]
+    fn same_best_moves() {
+        let mut game = three_by_three::TTT::new('x', 'o');
+        game.make_move(&three_by_three::Move { player: 'x', to_position: 0 });
+        let (pruned, _) = get_best_moves(game, 0, false);
+        let (unpruned, _) = try_get_best_moves_minimax(game, 0, false, &SearchOptions::default()).unwrap();
+        let pruned: Vec<_> = pruned.iter().map(|m| (m.game_move, m.score)).collect();
+        let unpruned: Vec<_> = unpruned.iter().map(|m| (m.game_move, m.score)).collect();
+        assert_eq!(pruned, unpruned);
+    }
+
+    #[test]
+    fn visits_every_position() {
+        let game = three_by_three::TTT::new('x', 'o');
+        let (_, pruned) = get_best_moves(game, 3, true);
+        let (_, unpruned) = try_get_best_moves_minimax(game, 3, true, &SearchOptions::default()).unwrap();
+        // 9 + 72 + 504 + 3024 positions below the starting moves
+        assert_eq!(unpruned.stats().nodes, 3609);
+        assert_eq!(unpruned.stats().cutoffs, 0);
+        assert!(pruned.stats().nodes < 3609);
+    }
+
+    #[test]
+    fn cross_checked() {
+        let mut game = three_by_three::TTT::new('x', 'o');
+        let (moves, _) = get_best_moves_cross_checked(game, 0, true);
+        assert_eq!(moves.len(), 9);
+        game.make_move(&three_by_three::Move { player: 'x', to_position: 4 });
+        get_best_moves_cross_checked(game, 0, false);
+
+        let mut game = four_by_four::TTT::new('x', 'o');
+        for (player, to_position) in [('x', 0), ('o', 5), ('x', 10)] {
+            game.make_move(&four_by_four::Move { player, to_position });
+        }
+        get_best_moves_cross_checked(game, 4, false);
+    }
+}
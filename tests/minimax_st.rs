@@ -1,3 +1,5 @@
+#![allow(clippy::needless_range_loop)]
+
 mod tests_single {
 
     use multithread_minimax::example::four_by_four::*;
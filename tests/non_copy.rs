@@ -0,0 +1,46 @@
+mod common;
+
+mod tests {
+
+    use crate::common::Wrapper;
+    use multithread_minimax::example::four_by_four::TTT;
+    use multithread_minimax::{get_best_moves, get_best_moves_multi};
+
+    /// Keeps every move made on the heap, so it cannot be `Copy`
+    fn get_board() -> Wrapper<'static, TTT> {
+        let mut game = TTT::new('x', 'o');
+        game.board = [
+            Some('x'),
+            Some('x'),
+            None,
+            None,
+            Some('o'),
+            Some('o'),
+            Some('o'),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None
+        ];
+        Wrapper::new(game)
+    }
+
+    #[test]
+    fn single_threaded() {
+        let (moves, _) = get_best_moves(get_board(), 0, true);
+        assert_eq!(moves.len(), 1);
+        assert_eq!(moves[0].game_move.to_position, 7);
+    }
+
+    #[test]
+    fn multi_threaded() {
+        let (moves, _) = get_best_moves_multi(get_board(), 0, true, 0);
+        assert_eq!(moves.len(), 1);
+        assert_eq!(moves[0].game_move.to_position, 7);
+    }
+}
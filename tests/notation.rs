@@ -0,0 +1,57 @@
+#[cfg(test)]
+mod tests {
+    use multithread_minimax::example::{four_by_four, three_by_three};
+    use multithread_minimax::{get_best_moves, Board, Notation};
+
+    #[test]
+    fn three_by_three_round_trip() {
+        let mut game = three_by_three::TTT::new('x', 'o');
+        assert_eq!(game.format_position(true), "3/3/3 x xo");
+        game.make_move(&"x0".parse().unwrap());
+        game.make_move(&"o4".parse().unwrap());
+        let text = game.format_position(true);
+        assert_eq!(text, "x2/1o1/3 x xo");
+        assert_eq!(three_by_three::TTT::parse_position(&text).unwrap(), (game, true));
+    }
+
+    #[test]
+    fn four_by_four_round_trip() {
+        let (game, is_max) = four_by_four::TTT::parse_position("xx2/1ooo/4/3x o xo").unwrap();
+        assert!(!is_max);
+        assert_eq!(game.board[0], Some('x'));
+        assert_eq!(game.board[5], Some('o'));
+        assert_eq!(game.board[15], Some('x'));
+        assert_eq!(game.format_position(false), "xx2/1ooo/4/3x o xo");
+    }
+
+    #[test]
+    fn prevent_win_from_position() {
+        let (game, is_max) = four_by_four::TTT::parse_position("xx2/4/ooo1/4 x xo").unwrap();
+        let (moves, _) = get_best_moves(game, 2, is_max);
+        assert_eq!(moves.len(), 1);
+        assert_eq!(moves[0].game_move.to_string(), "x11");
+    }
+
+    #[test]
+    fn moves() {
+        let game = three_by_three::TTT::new('x', 'o');
+        let m: three_by_three::Move = "o7".parse().unwrap();
+        assert_eq!((m.player, m.to_position), ('o', 7));
+        assert_eq!(m.to_string(), "o7");
+        assert_eq!(game.parse_move("7", false).unwrap(), m);
+        assert_eq!(game.format_move(&m), "o7");
+        assert!("7".parse::<three_by_three::Move>().is_err());
+        assert!("x16".parse::<four_by_four::Move>().is_err());
+        assert!("x15".parse::<four_by_four::Move>().is_ok());
+    }
+
+    #[test]
+    fn errors() {
+        assert!(three_by_three::TTT::parse_position("").is_err());
+        assert!(three_by_three::TTT::parse_position("3/3/3 x").is_err());
+        assert!(three_by_three::TTT::parse_position("4/4/4/4 x xo").is_err());
+        assert!(four_by_four::TTT::parse_position("3/3/3 x xo").is_err());
+        let error = three_by_three::TTT::parse_position("3/3/z2 x xo").unwrap_err();
+        assert_eq!(error.to_string(), "'z' is not one of the players");
+    }
+}
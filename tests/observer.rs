@@ -0,0 +1,128 @@
+mod tests {
+
+    use std::fmt::Debug;
+    use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+    use std::sync::{Arc, Condvar, Mutex};
+    use std::time::Duration;
+    use multithread_minimax::example::three_by_three::TTT;
+    use multithread_minimax::{
+        try_get_best_moves, try_get_best_moves_multi, try_get_best_moves_scoped, SearchObserver,
+        SearchOptions, SearchStats,
+    };
+
+    #[derive(Default)]
+    struct Recorder {
+        events: Mutex<Vec<String>>,
+        cutoffs: AtomicU64,
+    }
+
+    impl Recorder {
+        fn push(&self, event: String) {
+            self.events.lock().unwrap().push(event);
+        }
+
+        fn count(&self, prefix: &str) -> usize {
+            self.events.lock().unwrap().iter().filter(|e| e.starts_with(prefix)).count()
+        }
+    }
+
+    impl SearchObserver for Recorder {
+        fn iteration_started(&self, depth: u16) {
+            self.push(format!("started {}", depth));
+        }
+
+        fn iteration_finished(&self, depth: u16, stats: &SearchStats) {
+            self.push(format!("finished {} {}", depth, stats.nodes));
+        }
+
+        fn best_move_changed(&self, game_move: &dyn Debug, score: &dyn Debug) {
+            self.push(format!("best {:?} {:?}", game_move, score));
+        }
+
+        fn root_move_searched(&self, game_move: &dyn Debug, score: &dyn Debug) {
+            self.push(format!("searched {:?} {:?}", game_move, score));
+        }
+
+        fn cutoff(&self, _depth: u16, _searched: usize) {
+            self.cutoffs.fetch_add(1, Ordering::Relaxed);
+        }
+
+        fn job_dispatched(&self, job: usize) {
+            self.push(format!("job {}", job));
+        }
+    }
+
+    fn options(recorder: &Arc<Recorder>) -> SearchOptions {
+        SearchOptions { observer: Some(recorder.clone()), ..SearchOptions::default() }
+    }
+
+    #[test]
+    fn single_threaded() {
+        let recorder = Arc::new(Recorder::default());
+        let (_, metadata) = try_get_best_moves(TTT::new('x', 'o'), 3, true, &options(&recorder)).unwrap();
+        let stats = metadata.stats();
+        let events = recorder.events.lock().unwrap().clone();
+        assert_eq!(events[0], "started 3");
+        assert!(events[1].starts_with("searched"));
+        assert_eq!(events[2].replace("best", "searched"), events[1]);
+        assert_eq!(events.last().unwrap(), &format!("finished 3 {}", stats.nodes));
+        assert_eq!(recorder.count("searched"), 9);
+        assert_eq!(recorder.count("job"), 0);
+        assert_eq!(recorder.cutoffs.load(Ordering::Relaxed), stats.cutoffs);
+    }
+
+    #[test]
+    fn multi_threaded() {
+        for search in [try_get_best_moves_multi::<TTT>, try_get_best_moves_scoped::<TTT>] {
+            let recorder = Arc::new(Recorder::default());
+            search(TTT::new('x', 'o'), 2, true, 2, &options(&recorder)).unwrap();
+            assert_eq!(recorder.count("job"), 2);
+            assert_eq!(recorder.count("searched"), 9);
+            assert!(recorder.count("best") >= 1);
+            assert_eq!(recorder.count("finished 2"), 1);
+        }
+    }
+
+    #[test]
+    fn no_finish_on_error() {
+        let recorder = Arc::new(Recorder::default());
+        let options = SearchOptions { max_nodes: Some(10), ..options(&recorder) };
+        assert!(try_get_best_moves(TTT::new('x', 'o'), 0, true, &options).is_err());
+        assert_eq!(recorder.count("started"), 1);
+        assert_eq!(recorder.count("finished"), 0);
+    }
+
+    /// Waits in each callback for a while for a second thread to be in
+    /// one at the same time
+    #[derive(Default)]
+    struct Meeting {
+        inside: Mutex<usize>,
+        arrived: Condvar,
+        met: AtomicBool,
+    }
+
+    impl SearchObserver for Meeting {
+        fn root_move_searched(&self, _game_move: &dyn Debug, _score: &dyn Debug) {
+            let mut inside = self.inside.lock().unwrap();
+            *inside += 1;
+            self.arrived.notify_all();
+            let (mut inside, _) = self.arrived
+                .wait_timeout_while(inside, Duration::from_millis(200), |inside| *inside < 2)
+                .unwrap();
+            if *inside >= 2 {
+                self.met.store(true, Ordering::Relaxed);
+            }
+            *inside -= 1;
+        }
+    }
+
+    #[test]
+    fn observer_outside_results_lock() {
+        // a thread waiting in the observer must not keep the others from
+        // recording their moves and calling it too
+        let meeting = Arc::new(Meeting::default());
+        let options = SearchOptions { observer: Some(meeting.clone()), ..SearchOptions::default() };
+        try_get_best_moves_scoped(TTT::new('x', 'o'), 2, true, 2, &options).unwrap();
+        assert!(meeting.met.load(Ordering::Relaxed));
+    }
+}
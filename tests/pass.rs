@@ -0,0 +1,121 @@
+mod tests {
+
+    use std::fmt::{self, Display};
+    use multithread_minimax::{
+        get_best_moves, get_best_moves_cross_checked, get_best_moves_multi, get_best_moves_scoped,
+        Board, Result, Value,
+    };
+
+    /// Each player has a number of moves left which add points for the
+    /// maximizer or take them away for the minimizer. A player who has
+    /// used up their moves passes until the other player is done too.
+    #[derive(Clone, Debug)]
+    struct Points {
+        max_moves: u8,
+        min_moves: u8,
+        total: i64,
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    enum Move {
+        Points(i64),
+        Pass,
+    }
+
+    struct PointsResult {
+        over: bool,
+        total: i64,
+    }
+
+    impl Result for PointsResult {
+        type Score = i64;
+
+        fn is_over(&self) -> bool {
+            self.over
+        }
+
+        fn score(&self) -> i64 {
+            self.total
+        }
+
+        /// The total is the outcome, nobody wins outright
+        fn winner(&self) -> Option<bool> {
+            None
+        }
+    }
+
+    impl Display for Points {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "{} ({} / {} moves left)", self.total, self.max_moves, self.min_moves)
+        }
+    }
+
+    impl Board for Points {
+        type Move = Move;
+        type Result = PointsResult;
+        type Undo = ();
+
+        fn make_move(&mut self, valid_move: &Move) {
+            if let Move::Points(p) = *valid_move {
+                if p > 0 { self.max_moves -= 1 } else { self.min_moves -= 1 }
+                self.total += p;
+            }
+        }
+
+        fn unmake_move(&mut self, made_move: &Move, _undo: ()) {
+            if let Move::Points(p) = *made_move {
+                if p > 0 { self.max_moves += 1 } else { self.min_moves += 1 }
+                self.total -= p;
+            }
+        }
+
+        fn get_valid_moves(&self, is_maximizer: bool) -> Vec<Move> {
+            match (is_maximizer, self.max_moves, self.min_moves) {
+                (true, 0, _) | (false, _, 0) => vec![],
+                (true, _, _) => vec![Move::Points(1), Move::Points(5)],
+                (false, _, _) => vec![Move::Points(-1), Move::Points(-2)],
+            }
+        }
+
+        fn pass_move(&self, _is_maximizer: bool) -> Option<Move> {
+            Some(Move::Pass)
+        }
+
+        fn evaluate(&self) -> PointsResult {
+            PointsResult {
+                over: self.max_moves == 0 && self.min_moves == 0,
+                total: self.total,
+            }
+        }
+    }
+
+    #[test]
+    fn opponent_passes() {
+        let board = Points { max_moves: 1, min_moves: 3, total: 0 };
+        for (moves, _) in [
+            get_best_moves(board.clone(), 0, true),
+            get_best_moves_multi(board.clone(), 0, true, 0),
+            get_best_moves_scoped(board.clone(), 0, true, 0),
+            get_best_moves_cross_checked(board.clone(), 0, true),
+        ] {
+            assert_eq!(moves.len(), 1);
+            assert_eq!(moves[0].game_move, Move::Points(5));
+            assert_eq!(moves[0].score, Value::Score(-1));
+        }
+    }
+
+    #[test]
+    fn pass_at_root() {
+        let board = Points { max_moves: 0, min_moves: 2, total: 0 };
+        for (moves, _) in [
+            get_best_moves(board.clone(), 0, true),
+            get_best_moves_multi(board.clone(), 0, true, 0),
+            get_best_moves_scoped(board.clone(), 0, true, 0),
+            get_best_moves_cross_checked(board.clone(), 0, true),
+        ] {
+            assert_eq!(moves.len(), 1);
+            assert_eq!(moves[0].game_move, Move::Pass);
+            assert_eq!(moves[0].score, Value::Score(-4));
+        }
+    }
+}
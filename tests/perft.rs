@@ -0,0 +1,48 @@
+mod tests {
+
+    use multithread_minimax::example::{four_by_four, three_by_three};
+    use multithread_minimax::{perft, perft_divide, perft_divide_multi, perft_multi, perft_verify, PerftMismatch};
+
+    #[test]
+    fn three_by_three_known_counts() {
+        let game = three_by_three::TTT::new('x', 'o');
+        assert_eq!(perft_verify(game, true, &three_by_three::PERFT), Ok(()));
+    }
+
+    #[test]
+    fn three_by_three_permutations_bound() {
+        let game = three_by_three::TTT::new('x', 'o');
+        let mut bound = 1;
+        for depth in 1..=9 {
+            bound *= 10 - depth as u64;
+            assert!(perft(game, depth, true) <= bound);
+        }
+    }
+
+    #[test]
+    fn mismatch() {
+        let game = three_by_three::TTT::new('x', 'o');
+        assert_eq!(
+            perft_verify(game, true, &[9, 71]),
+            Err(PerftMismatch { depth: 2, expected: 71, found: 72 })
+        );
+    }
+
+    #[test]
+    fn divide() {
+        let game = four_by_four::TTT::new('x', 'o');
+        let divided = perft_divide(game, 3, true);
+        assert_eq!(divided.len(), 16);
+        assert!(divided.iter().all(|(_, nodes)| *nodes == 210));
+        assert_eq!(divided.iter().map(|(_, nodes)| nodes).sum::<u64>(), perft(game, 3, true));
+    }
+
+    #[test]
+    fn multi_threaded() {
+        let game = three_by_three::TTT::new('x', 'o');
+        assert_eq!(perft_multi(game, 9, true, 0), Ok(three_by_three::PERFT[8]));
+        let single = perft_divide(game, 6, true);
+        let multi = perft_divide_multi(game, 6, true, 0).unwrap();
+        assert_eq!(single, multi);
+    }
+}
@@ -0,0 +1,172 @@
+mod tests {
+
+    use std::fmt::{self, Display};
+    use std::io::{self, Write};
+    use std::sync::{Arc, Mutex};
+    use multithread_minimax::{Board, Notation, NotationError, Protocol, Result};
+
+    /// Players take one to three stones from a pile, whoever takes the
+    /// last stone wins. Positions are written as the pile and the player
+    /// to move, like `7 max`. Evaluating a pile of 99 panics.
+    #[derive(Clone, Debug)]
+    struct Nim {
+        stones: u32,
+        max_took_last: bool,
+    }
+
+    struct NimResult(Option<bool>);
+
+    impl Result for NimResult {
+        type Score = i32;
+
+        fn is_over(&self) -> bool {
+            self.0.is_some()
+        }
+
+        fn score(&self) -> i32 {
+            match self.0 {
+                Some(true) => 1,
+                Some(false) => -1,
+                None => 0,
+            }
+        }
+    }
+
+    impl Display for Nim {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "{} stones", self.stones)
+        }
+    }
+
+    impl Board for Nim {
+        type Move = (u32, bool);
+        type Result = NimResult;
+        type Undo = bool;
+
+        fn make_move(&mut self, &(take, is_max): &(u32, bool)) -> bool {
+            self.stones -= take;
+            std::mem::replace(&mut self.max_took_last, is_max)
+        }
+
+        fn unmake_move(&mut self, &(take, _): &(u32, bool), undo: bool) {
+            self.stones += take;
+            self.max_took_last = undo;
+        }
+
+        fn get_valid_moves(&self, is_maximizer: bool) -> Vec<(u32, bool)> {
+            (1..=self.stones.min(3)).map(|take| (take, is_maximizer)).collect()
+        }
+
+        fn evaluate(&self) -> NimResult {
+            assert!(self.stones != 99, "99 stones");
+            NimResult(if self.stones == 0 { Some(self.max_took_last) } else { None })
+        }
+    }
+
+    impl Notation for Nim {
+        fn parse_position(text: &str) -> std::result::Result<(Nim, bool), NotationError> {
+            let error = || NotationError(format!("bad position {}", text));
+            let (stones, side) = text.split_once(' ').ok_or_else(error)?;
+            let stones = stones.parse().map_err(|_| error())?;
+            let is_max = match side {
+                "max" => true,
+                "min" => false,
+                _ => return Err(error()),
+            };
+            Ok((Nim { stones, max_took_last: !is_max }, is_max))
+        }
+
+        fn format_position(&self, is_maximizers_turn: bool) -> String {
+            format!("{} {}", self.stones, if is_maximizers_turn { "max" } else { "min" })
+        }
+
+        fn parse_move(
+            &self,
+            text: &str,
+            is_maximizers_turn: bool
+        ) -> std::result::Result<(u32, bool), NotationError> {
+            let take = text.parse().map_err(|_| NotationError(format!("bad move {}", text)))?;
+            Ok((take, is_maximizers_turn))
+        }
+
+        fn format_move(&self, game_move: &(u32, bool)) -> String {
+            game_move.0.to_string()
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct Output(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Output {
+        fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(bytes)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn run(commands: &str) -> Vec<String> {
+        let output = Output::default();
+        let mut protocol = Protocol::new(Nim { stones: 10, max_took_last: false }, true, 2);
+        protocol.run(commands.as_bytes(), output.clone()).unwrap();
+        let text = String::from_utf8(output.0.lock().unwrap().clone()).unwrap();
+        text.lines().map(String::from).collect()
+    }
+
+    #[test]
+    fn search_to_depth() {
+        let lines = run("position startpos\ngo depth 2\n");
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("info depth 1 score 0 nodes "));
+        assert!(lines[1].starts_with("info depth 2 score "));
+        assert!(lines[2].starts_with("bestmove "));
+    }
+
+    #[test]
+    fn ready_during_search() {
+        let lines = run("position 40 max\ngo\nisready\nstop\n");
+        let ready = lines.iter().position(|line| line == "readyok").unwrap();
+        let best = lines.iter().position(|line| line.starts_with("bestmove ")).unwrap();
+        assert!(ready < best, "{:?}", lines);
+    }
+
+    #[test]
+    fn panicking_search() {
+        let lines = run("position 99 max\ngo\nisready\n");
+        assert!(lines.contains(&String::from("bestmove none")), "{:?}", lines);
+        assert!(lines.contains(&String::from("readyok")), "{:?}", lines);
+    }
+
+    #[test]
+    fn forced_win() {
+        // taking one leaves a multiple of four, which loses
+        let lines = run("position 5 min\ngo\n");
+        let info = lines.iter().rev().find(|line| line.starts_with("info")).unwrap();
+        assert!(info.contains("score loss "), "{}", info);
+        assert_eq!(info.split(" pv ").nth(1).unwrap().split(' ').count(), 3, "{}", info);
+        assert_eq!(lines.last().unwrap(), "bestmove 1");
+    }
+
+    #[test]
+    fn moves_and_errors() {
+        let commands = "position startpos moves 2 3 4\nposition startpos moves 2 2\nflip\ngo depth 1 nodes x\ngo\n";
+        let lines = run(commands);
+        assert_eq!(lines[0], "info string illegal move 4");
+        assert_eq!(lines[1], "info string unknown command flip");
+        assert_eq!(lines[2], "info string nodes needs a number");
+        // 6 stones left, so taking 2 wins
+        assert_eq!(lines.last().unwrap(), "bestmove 2");
+    }
+
+    #[test]
+    fn limits() {
+        let lines = run("position 40 max\ngo nodes 50\n");
+        assert!(lines.last().unwrap().starts_with("bestmove "));
+        let lines = run("position 40 max\ngo\nstop\n");
+        assert!(lines.last().unwrap().starts_with("bestmove "));
+        let lines = run("position 0 max\ngo movetime 10\n");
+        assert_eq!(lines, vec!["bestmove none"]);
+    }
+}
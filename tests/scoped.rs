@@ -0,0 +1,38 @@
+mod common;
+
+mod tests {
+
+    use crate::common::Wrapper;
+    use multithread_minimax::example::four_by_four::TTT;
+    use multithread_minimax::get_best_moves_scoped;
+
+    #[test]
+    fn borrowed_board() {
+        // center squares first
+        let order: Vec<usize> = vec![5, 6, 9, 10, 0, 3, 12, 15, 1, 2, 4, 7, 8, 11, 13, 14];
+        let mut game = TTT::new('o', 'x');
+        game.board = [
+            Some('o'),
+            Some('o'),
+            Some('o'),
+            Some('x'),
+            None,
+            None,
+            None,
+            Some('x'),
+            None,
+            None,
+            None,
+            Some('x'),
+            None,
+            None,
+            None,
+            None
+        ];
+        // generates moves in the order of a table it borrows
+        let board = Wrapper { order: Some(&order), ..Wrapper::new(game) };
+        let (moves, _) = get_best_moves_scoped(board, 0, false, 0);
+        assert_eq!(moves.len(), 1);
+        assert_eq!(moves[0].game_move.to_position, 15);
+    }
+}
@@ -0,0 +1,114 @@
+#[cfg(test)]
+mod tests {
+    use multithread_minimax::example::three_by_three::TTT;
+    use multithread_minimax::{read_samples, write_samples, Board, Result, SelfPlay, Value};
+    use std::fmt::{self, Display};
+
+    /// A game that never ends, where each player adds one to a counter
+    /// the maximizer wants as high as possible
+    #[derive(Clone, Debug)]
+    struct Counter(i32);
+
+    struct CounterResult(i32);
+
+    impl Result for CounterResult {
+        type Score = i32;
+
+        fn is_over(&self) -> bool {
+            false
+        }
+
+        fn score(&self) -> i32 {
+            self.0
+        }
+
+        fn winner(&self) -> Option<bool> {
+            panic!("winner called on a game that is not over")
+        }
+    }
+
+    impl Display for Counter {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "{}", self.0)
+        }
+    }
+
+    impl Board for Counter {
+        type Move = ();
+        type Result = CounterResult;
+        type Undo = ();
+
+        fn make_move(&mut self, _: &()) {
+            self.0 += 1;
+        }
+
+        fn unmake_move(&mut self, _: &(), _: ()) {
+            self.0 -= 1;
+        }
+
+        fn get_valid_moves(&self, _: bool) -> Vec<()> {
+            vec![()]
+        }
+
+        fn evaluate(&self) -> CounterResult {
+            CounterResult(self.0)
+        }
+    }
+
+    #[test]
+    fn perfect_play_draws() {
+        let selfplay = SelfPlay { games: 4, threads: 1, ..SelfPlay::default() };
+        let samples = selfplay.play(&TTT::new('x', 'o'), true).unwrap();
+        // every drawn game fills the board
+        assert_eq!(samples.len(), 4 * 9);
+        for sample in &samples {
+            assert_eq!(sample.score, Value::Score(0));
+            assert_eq!(sample.outcome, None);
+        }
+        assert!(samples.iter().step_by(9).all(|sample| sample.is_maximizers_turn));
+        // the equally best first moves are picked at random
+        let first_moves: Vec<_> = samples.iter().step_by(9).map(|sample| sample.game_move).collect();
+        assert!(first_moves.iter().any(|m| *m != first_moves[0]));
+    }
+
+    #[test]
+    fn exploration_loses_games() {
+        let selfplay = SelfPlay { games: 10, threads: 1, exploration: 0.5, ..SelfPlay::default() };
+        let samples = selfplay.play(&TTT::new('x', 'o'), true).unwrap();
+        assert!(samples.iter().any(|sample| sample.outcome.is_some()));
+        for sample in &samples {
+            assert!(sample.board.get_valid_moves(sample.is_maximizers_turn).contains(&sample.game_move));
+        }
+    }
+
+    #[test]
+    fn unfinished_games_are_draws() {
+        let selfplay = SelfPlay { games: 2, max_depth: 1, threads: 1, max_plies: 5, ..SelfPlay::default() };
+        let samples = selfplay.play(&Counter(0), true).unwrap();
+        assert_eq!(samples.len(), 2 * 5);
+        assert!(samples.iter().all(|sample| sample.outcome.is_none()));
+    }
+
+    #[test]
+    fn round_trip() {
+        let selfplay = SelfPlay { games: 3, max_depth: 3, threads: 1, exploration: 0.3, ..SelfPlay::default() };
+        let samples = selfplay.play(&TTT::new('x', 'o'), true).unwrap();
+        let mut file = vec![];
+        write_samples(&mut file, &samples).unwrap();
+        let text = String::from_utf8(file.clone()).unwrap();
+        assert!(text.starts_with("3/3/3 x xo\tx"));
+        assert_eq!(text.lines().count(), samples.len());
+
+        let read = read_samples::<TTT>(&file[..]).unwrap();
+        assert_eq!(read.len(), samples.len());
+        for (read, sample) in read.iter().zip(&samples) {
+            assert_eq!(read.board, sample.board);
+            assert_eq!(read.is_maximizers_turn, sample.is_maximizers_turn);
+            assert_eq!(read.game_move, sample.game_move);
+            assert_eq!(read.score, sample.score);
+            assert_eq!(read.outcome, sample.outcome);
+        }
+        assert!(read_samples::<TTT>(&b"3/3/3 x xo\tx4\t0\n"[..]).is_err());
+        assert!(read_samples::<TTT>(&b"3/3/3 x xo\tx4\twin 2\t2\n"[..]).is_err());
+    }
+}
@@ -0,0 +1,46 @@
+#[cfg(test)]
+mod tests {
+    use multithread_minimax::example::{four_by_four, three_by_three};
+    use multithread_minimax::{get_best_moves, Board, MoveScore, SearchStats, Value};
+
+    #[test]
+    fn boards() {
+        let mut game = three_by_three::TTT::new('x', 'o');
+        game.make_move(&three_by_three::Move { player: 'x', to_position: 4 });
+        let json = serde_json::to_string(&game).unwrap();
+        assert_eq!(serde_json::from_str::<three_by_three::TTT>(&json).unwrap(), game);
+
+        let game = four_by_four::TTT::new('a', 'b');
+        let json = serde_json::to_string(&game).unwrap();
+        assert_eq!(serde_json::from_str::<four_by_four::TTT>(&json).unwrap(), game);
+
+        let m = four_by_four::Move { player: 'b', to_position: 15 };
+        assert_eq!(serde_json::to_string(&m).unwrap(), r#"{"player":"b","to_position":15}"#);
+        let result = game.evaluate();
+        let json = serde_json::to_string(&result).unwrap();
+        assert!(serde_json::from_str::<four_by_four::TttResult>(&json).is_ok());
+    }
+
+    #[test]
+    fn search_results() {
+        let (moves, metadata) = get_best_moves(three_by_three::TTT::new('x', 'o'), 2, true);
+        let json = serde_json::to_string(&moves).unwrap();
+        let read: Vec<MoveScore<three_by_three::TTT>> = serde_json::from_str(&json).unwrap();
+        assert_eq!(read.len(), moves.len());
+        for (read, m) in read.iter().zip(&moves) {
+            assert_eq!(read.game_move, m.game_move);
+            assert_eq!(read.score, m.score);
+            assert_eq!(read.pv, m.pv);
+        }
+
+        let stats = metadata.stats();
+        let json = serde_json::to_string(&stats).unwrap();
+        assert_eq!(serde_json::from_str::<SearchStats>(&json).unwrap(), stats);
+    }
+
+    #[test]
+    fn values() {
+        assert_eq!(serde_json::to_string(&Value::<i64>::Win(3)).unwrap(), r#"{"Win":3}"#);
+        assert_eq!(serde_json::from_str::<Value<i64>>(r#"{"Score":-2}"#).unwrap(), Value::Score(-2));
+    }
+}
@@ -0,0 +1,62 @@
+mod tests {
+
+    use multithread_minimax::example::three_by_three::TTT;
+    use multithread_minimax::{get_best_moves, get_best_moves_multi, get_best_moves_scoped};
+
+    #[test]
+    fn single_threaded() {
+        let (_, metadata) = get_best_moves(TTT::new('x', 'o'), 0, true);
+        let stats = metadata.stats();
+        assert_eq!(stats.max_depth, 9);
+        assert!(stats.cutoffs > 0);
+        assert_eq!(stats.threads.len(), 1);
+        assert_eq!(stats.threads[0].nodes, stats.nodes);
+        assert_eq!(stats.threads[0].cutoffs, stats.cutoffs);
+        assert_eq!(metadata.stats(), stats);
+    }
+
+    #[test]
+    fn limited_depth() {
+        let (_, metadata) = get_best_moves(TTT::new('x', 'o'), 3, true);
+        assert_eq!(metadata.stats().max_depth, 4);
+    }
+
+    #[test]
+    fn per_thread() {
+        for (_, metadata) in [
+            get_best_moves_multi(TTT::new('x', 'o'), 0, true, 2),
+            get_best_moves_scoped(TTT::new('x', 'o'), 0, true, 2),
+        ] {
+            let stats = metadata.stats();
+            assert_eq!(stats.threads.len(), 2);
+            assert_eq!(stats.threads.iter().map(|thread| thread.nodes).sum::<u64>(), stats.nodes);
+            assert_eq!(stats.max_depth, 9);
+            assert!(stats.to_string().contains(&format!("nodes {}", stats.nodes)));
+        }
+    }
+
+    #[test]
+    fn per_ply() {
+        let (_, single) = get_best_moves(TTT::new('x', 'o'), 0, true);
+        let (_, multi) = get_best_moves_multi(TTT::new('x', 'o'), 0, true, 2);
+        for stats in [single.stats(), multi.stats()] {
+            assert_eq!(stats.plies.len(), 9);
+            assert_eq!(stats.plies[0].nodes, 9);
+            assert_eq!(stats.plies.iter().map(|ply| ply.nodes).sum::<u64>(), stats.nodes);
+            // nobody can win before the fifth move
+            assert!(stats.plies[..4].iter().all(|ply| ply.terminal == 0));
+            assert!(stats.plies[4].terminal > 0);
+            for ply in &stats.plies {
+                assert!(ply.first_move_cutoffs <= ply.cutoffs);
+                assert!((0.0..=1.0).contains(&ply.first_move_cutoff_rate()));
+            }
+            let ratios = stats.ply_ratios();
+            assert_eq!(ratios.len(), 8);
+            assert_eq!(ratios[0], stats.plies[1].nodes as f64 / 9.0);
+            let ebf = stats.effective_branching_factor();
+            assert_eq!(ebf, (stats.nodes as f64).powf(1.0 / 9.0));
+            // fewer than the 9 moves of the first ply, thanks to pruning
+            assert!(ebf > 1.0 && ebf < 9.0);
+        }
+    }
+}
@@ -0,0 +1,47 @@
+#[cfg(test)]
+mod tests {
+    use multithread_minimax::example::three_by_three::TTT;
+    use multithread_minimax::{play_match, Engine, MatchOptions, Sprt, SprtResult};
+
+    #[test]
+    fn perfect_play_draws() {
+        let game = TTT::new('x', 'o');
+        let perfect = Engine::with_depth("perfect", 0);
+        let options = MatchOptions { games: 10, opening_plies: 0, ..MatchOptions::default() };
+        let result = play_match(&game, true, &perfect, &Engine::with_depth("also perfect", 0), &options).unwrap();
+        assert_eq!((result.wins, result.draws, result.losses), (0, 10, 0));
+        assert_eq!(result.elo().0, 0.0);
+
+        // a random opening can be lost, but each is played from both sides
+        let options = MatchOptions { games: 10, ..MatchOptions::default() };
+        let result = play_match(&game, true, &perfect, &Engine::with_depth("also perfect", 0), &options).unwrap();
+        assert_eq!(result.wins, result.losses);
+    }
+
+    #[test]
+    fn deeper_search_never_loses() {
+        let game = TTT::new('x', 'o');
+        let (deep, shallow) = (Engine::with_depth("deep", 0), Engine::with_depth("shallow", 1));
+        let options = MatchOptions { games: 40, opening_plies: 1, ..MatchOptions::default() };
+        let result = play_match(&game, true, &deep, &shallow, &options).unwrap();
+        assert_eq!(result.games(), 40);
+        assert_eq!(result.losses, 0);
+        assert!(result.wins > 0);
+        assert!(result.elo().0 > 0.0);
+
+        let reversed = play_match(&game, true, &shallow, &deep, &options).unwrap();
+        assert_eq!((reversed.wins, reversed.losses), (result.losses, result.wins));
+    }
+
+    #[test]
+    fn sprt_stops_early() {
+        let game = TTT::new('x', 'o');
+        let (deep, shallow) = (Engine::with_depth("deep", 0), Engine::with_depth("shallow", 1));
+        let sprt = Sprt { elo0: 0.0, elo1: 50.0, ..Sprt::default() };
+        let options = MatchOptions { games: 1000, opening_plies: 1, sprt: Some(sprt), ..MatchOptions::default() };
+        let result = play_match(&game, true, &deep, &shallow, &options).unwrap();
+        assert_eq!(result.sprt, Some(SprtResult::H1));
+        assert!(result.games() < 1000);
+        assert!(result.to_string().ends_with(" sprt H1"));
+    }
+}
@@ -0,0 +1,118 @@
+mod tests {
+
+    use std::sync::Arc;
+    use multithread_minimax::example::three_by_three::{Move, TTT};
+    use multithread_minimax::{
+        try_get_best_moves, try_get_best_moves_multi, Board, SearchOptions, Tree, TreeNode,
+        TreeRecorder,
+    };
+    use serde_json::{json, Value};
+
+    fn options(recorder: &Arc<TreeRecorder>) -> SearchOptions {
+        SearchOptions { observer: Some(recorder.clone()), ..SearchOptions::default() }
+    }
+
+    #[test]
+    fn records_search() {
+        let recorder = Arc::new(TreeRecorder::new(u16::MAX, usize::MAX));
+        let (_, metadata) = try_get_best_moves(TTT::new('x', 'o'), 2, true, &options(&recorder)).unwrap();
+        let tree = recorder.tree();
+        // every position visited plus the root
+        assert_eq!(tree.nodes.len() as u64, metadata.stats().nodes + 1);
+        assert_eq!(tree.nodes[0].children.len(), 9);
+        let first = &tree.nodes[tree.nodes[0].children[0]];
+        assert_eq!(first.game_move, format!("{:?}", Move { player: 'x', to_position: 0 }));
+        assert_eq!(first.alpha, "Loss(0)");
+        assert_eq!(first.beta, "Win(0)");
+        assert_eq!(first.score.as_deref(), Some("Score(0)"));
+        assert!(tree.nodes.iter().all(|node| node.depth.is_none() || node.score.is_some()));
+        assert!(tree.nodes.iter().any(|node| node.cutoff));
+    }
+
+    #[test]
+    fn limits() {
+        let recorder = Arc::new(TreeRecorder::new(0, usize::MAX));
+        try_get_best_moves(TTT::new('x', 'o'), 3, true, &options(&recorder)).unwrap();
+        assert_eq!(recorder.tree().nodes.len(), 10);
+
+        let recorder = Arc::new(TreeRecorder::new(u16::MAX, 5));
+        try_get_best_moves(TTT::new('x', 'o'), 3, true, &options(&recorder)).unwrap();
+        assert_eq!(recorder.tree().nodes.len(), 6);
+    }
+
+    #[test]
+    fn multi_threaded() {
+        let recorder = Arc::new(TreeRecorder::new(1, usize::MAX));
+        try_get_best_moves_multi(TTT::new('x', 'o'), 2, true, 4, &options(&recorder)).unwrap();
+        let tree = recorder.tree();
+        assert_eq!(tree.nodes[0].children.len(), 9);
+        for child in &tree.nodes[0].children {
+            assert_eq!(tree.nodes[*child].children.len(), 8);
+        }
+    }
+
+    #[test]
+    fn exporters() {
+        let mut game = TTT::new('x', 'o');
+        for (player, to_position) in [('x', 0), ('o', 4), ('x', 8), ('o', 2), ('x', 6)] {
+            game.make_move(&Move { player, to_position });
+        }
+        let recorder = Arc::new(TreeRecorder::new(u16::MAX, usize::MAX));
+        try_get_best_moves(game, 0, false, &options(&recorder)).unwrap();
+        let tree = recorder.tree();
+
+        let dot = tree.to_dot();
+        assert!(dot.starts_with("digraph tree {"));
+        assert!(dot.contains("n0 -> n1;"));
+        assert!(dot.contains("to_position: 1"));
+
+        let json: Value = serde_json::from_str(&tree.to_json()).unwrap();
+        assert_eq!(json, expected_json(&tree, 0));
+        assert_eq!(json["move"], "root");
+        assert_eq!(json["depth"], Value::Null);
+        assert_eq!(json["children"][0]["depth"], 0);
+        assert_eq!(json["children"][0]["alpha"], "Loss(0)");
+        assert_eq!(json["children"][0]["beta"], "Win(0)");
+    }
+
+    /// The JSON a node and the nodes below it should be written as
+    fn expected_json(tree: &Tree, index: usize) -> Value {
+        let node = &tree.nodes[index];
+        let children: Vec<_> = node.children.iter().map(|child| expected_json(tree, *child)).collect();
+        json!({
+            "move": node.game_move,
+            "depth": node.depth,
+            "alpha": node.alpha,
+            "beta": node.beta,
+            "score": node.score,
+            "cutoff": node.cutoff,
+            "children": children,
+        })
+    }
+
+    #[test]
+    fn json_escaping() {
+        let node = |game_move: &str, depth, score: Option<&str>, children| TreeNode {
+            game_move: String::from(game_move),
+            depth,
+            alpha: String::from("\"a\" \\ b"),
+            beta: String::from("tab\there"),
+            score: score.map(String::from),
+            cutoff: depth.is_some(),
+            children,
+        };
+        let tree = Tree {
+            nodes: vec![
+                node("root", None, None, vec![1, 2]),
+                node("Move { name: \"x\" }\nnext", Some(0), Some("Score(-1)"), vec![3]),
+                node("\u{1}", Some(0), None, vec![]),
+                node("back\\slash", Some(1), Some("Win(2)"), vec![]),
+            ],
+        };
+        let json: Value = serde_json::from_str(&tree.to_json()).unwrap();
+        assert_eq!(json, expected_json(&tree, 0));
+        assert_eq!(json["children"][0]["move"], "Move { name: \"x\" }\nnext");
+        assert_eq!(json["children"][0]["children"][0]["move"], "back\\slash");
+        assert_eq!(json["children"][1]["score"], Value::Null);
+    }
+}
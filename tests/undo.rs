@@ -0,0 +1,54 @@
+mod common;
+
+mod tests {
+
+    use crate::common::Wrapper;
+    use multithread_minimax::example::four_by_four::TTT;
+    use multithread_minimax::{get_best_moves, get_best_moves_multi, get_best_moves_scoped};
+
+    /// Can only be unmade by restoring the copy of the game returned
+    /// from `make_move`, as if every move were irreversible
+    fn get_board() -> Wrapper<'static, TTT> {
+        let mut game = TTT::new('x', 'o');
+        game.board = [
+            Some('o'),
+            Some('o'),
+            Some('o'),
+            None,
+            Some('x'),
+            Some('x'),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None
+        ];
+        Wrapper { snapshots: true, ..Wrapper::new(game) }
+    }
+
+    #[test]
+    fn single_threaded() {
+        let (moves, _) = get_best_moves(get_board(), 0, true);
+        assert_eq!(moves.len(), 1);
+        assert_eq!(moves[0].game_move.to_position, 3);
+    }
+
+    #[test]
+    fn multi_threaded() {
+        let (moves, _) = get_best_moves_multi(get_board(), 0, true, 0);
+        assert_eq!(moves.len(), 1);
+        assert_eq!(moves[0].game_move.to_position, 3);
+    }
+
+    #[test]
+    fn scoped() {
+        let (moves, _) = get_best_moves_scoped(get_board(), 0, true, 0);
+        assert_eq!(moves.len(), 1);
+        assert_eq!(moves[0].game_move.to_position, 3);
+    }
+}